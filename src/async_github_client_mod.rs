@@ -0,0 +1,98 @@
+// async_github_client_mod.rs
+
+//! Async trait for the GitHubClient in applications that already run inside a tokio runtime.
+//!
+//! The reqwest::blocking client panics inside an async runtime, so these applications need the async API.
+//! All the functions with the `_async` suffix use the SendToGitHubApiAsync trait.
+//! The blocking functions are thin wrappers: they run the same async functions
+//! with the blocking GitHubClient wrapped in the BlockingGitHubClient adapter.
+
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+use crate::error_mod::ResultWithGitHubLibError;
+use crate::github_request_mod::GitHubRequest;
+use crate::github_response_mod::GitHubResponse;
+#[cfg(feature = "blocking")]
+use crate::http_client_mod::http_client;
+
+/// async trait for GitHubClient in the calling crate
+pub trait SendToGitHubApiAsync {
+    /// Send github api request and return the status code, headers and json body
+    ///
+    /// This function encapsulates the secret API secret_token.
+    /// The RequestBuilder is created somewhere in the library crate.
+    /// The client can be passed to the library. It will not reveal the secret_token.
+    /// The same function sends also the uploads with the stream body.
+    /// Implement it with GitHubResponse::from_response().
+    fn send_to_github_api_async(&self, req: reqwest::RequestBuilder) -> impl std::future::Future<Output = GitHubResponse> + Send;
+
+    /// Authorize the request before it is sent
+    ///
+    /// The library calls this for the requests it sends with the description of the operation, method, path and body.
+    /// The default allows all requests.
+    fn authorize_github_request(&self, _github_request: &GitHubRequest) -> ResultWithGitHubLibError<()> {
+        Ok(())
+    }
+
+    /// Is this client only planning the requests, like the DryRunGitHubClient
+    ///
+    /// In the dry run the library does not change local files and does not run git commands that change the repository.
+    fn is_dry_run(&self) -> bool {
+        false
+    }
+}
+
+/// Adapter to use the blocking GitHubClient with the async functions
+///
+/// The requests are sent immediately with the blocking client and the future is already ready.
+/// The uploads with the stream body go to upload_to_github().
+#[cfg(feature = "blocking")]
+pub(crate) struct BlockingGitHubClient<'a, C: SendToGitHubApi> {
+    github_client: &'a C,
+}
+
+#[cfg(feature = "blocking")]
+impl<'a, C: SendToGitHubApi> BlockingGitHubClient<'a, C> {
+    /// Wrap the blocking GitHubClient
+    pub(crate) fn new(github_client: &'a C) -> Self {
+        BlockingGitHubClient { github_client }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<C: SendToGitHubApi> SendToGitHubApiAsync for BlockingGitHubClient<'_, C> {
+    fn send_to_github_api_async(&self, req: reqwest::RequestBuilder) -> impl std::future::Future<Output = GitHubResponse> + Send {
+        // only the request with the stream body cannot be cloned
+        let Some(req_for_blocking) = req.try_clone() else {
+            let upload = self.github_client.upload_to_github(req);
+            return futures_util::future::Either::Left(async move { GitHubResponse::from_json(upload.await) });
+        };
+        let github_response = match req_for_blocking.build() {
+            Ok(request) => self
+                .github_client
+                .send_to_github_api_with_response(blocking_request_builder(&request)),
+            Err(e) => GitHubResponse::from_json(serde_json::json!({ "message": e.to_string() })),
+        };
+        futures_util::future::Either::Right(std::future::ready(github_response))
+    }
+
+    fn authorize_github_request(&self, github_request: &GitHubRequest) -> ResultWithGitHubLibError<()> {
+        self.github_client.authorize_github_request(github_request)
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.github_client.is_dry_run()
+    }
+}
+
+/// The blocking request builder with the same method, url, headers and body as the async request
+#[cfg(feature = "blocking")]
+fn blocking_request_builder(request: &reqwest::Request) -> reqwest::blocking::RequestBuilder {
+    let req = http_client()
+        .request(request.method().clone(), request.url().clone())
+        .headers(request.headers().clone());
+    match request.body().and_then(|body| body.as_bytes()) {
+        Some(body) => req.body(body.to_vec()),
+        None => req,
+    }
+}
@@ -5,16 +5,66 @@
 //! Pass the function send_to_github_api() as a parameter. It encapsulates the secret_token.
 
 use cargo_auto_lib as cl;
-// traits must be in scope (Rust strangeness)
-use cl::CargoTomlPublicApiMethods;
-
+#[cfg(any(feature = "blocking", feature = "async"))]
 use cl::ShellCommandLimitedDoubleQuotesSanitizerTrait;
+#[cfg(any(feature = "blocking", feature = "async"))]
 use cl::BLUE;
 use cl::RED;
 use cl::RESET;
+#[cfg(any(feature = "blocking", feature = "async"))]
 use cl::YELLOW;
 
+#[cfg(feature = "blocking")]
+use crate::async_github_client_mod::BlockingGitHubClient;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+use crate::error_mod::GitHubLibError;
+use crate::error_mod::ResultWithGitHubLibError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_endpoint_mod::github_endpoint;
+use crate::github_headers_mod::github_headers;
+#[cfg(feature = "blocking")]
+use crate::github_metadata_mod::cargo_toml_github_metadata;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_metadata_mod::cargo_toml_package_metadata;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_metadata_mod::category_to_topic;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_metadata_mod::repository_metadata_changes;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_metadata_mod::CargoTomlPackageMetadata;
+use crate::github_metadata_mod::GitHubMetadata;
+use crate::github_metadata_mod::PagesSource;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_metadata_mod::RepositoryMetadataChange;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_request_mod::GitHubRequest;
+#[cfg(feature = "blocking")]
+use crate::github_response_mod::GitHubResponse;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::send_to_github_api_as_async;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::Organization;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::Repository;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::Topics;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::User;
+use crate::http_client_mod::async_http_client;
+#[cfg(feature = "blocking")]
+use crate::http_client_mod::http_client;
+#[cfg(all(feature = "blocking", feature = "interactive"))]
+use crate::prompter_mod::InquirePrompter;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::prompter_mod::Prompter;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::retry_mod::send_to_github_api_with_retry_async;
+#[cfg(feature = "blocking")]
+use crate::utils_mod::block_on;
+
 /// trait for GitHubClient in the calling crate
+#[cfg(feature = "blocking")]
 pub trait SendToGitHubApi {
     /// Send github api request
     ///
@@ -23,6 +73,33 @@ pub trait SendToGitHubApi {
     /// The client can be passed to the library. It will not reveal the secret_token.
     fn send_to_github_api(&self, req: reqwest::blocking::RequestBuilder) -> serde_json::Value;
 
+    /// Send github api request and return the status code, headers and json body
+    ///
+    /// This function encapsulates the secret API secret_token.
+    /// The library sends all the requests with this function.
+    /// The default adapter calls send_to_github_api() and guesses the status from the json.
+    /// Implement it with GitHubResponse::from_blocking_response() to get the real status and headers.
+    fn send_to_github_api_with_response(&self, req: reqwest::blocking::RequestBuilder) -> GitHubResponse {
+        GitHubResponse::from_json(self.send_to_github_api(req))
+    }
+
+    /// Authorize the request before it is sent
+    ///
+    /// The library calls this for the requests it sends with the description of the operation, method, path and body.
+    /// The GitHubClient can return an error for the operations it does not allow, like with GitHubRequestAllowlist.
+    /// The hook is only advisory, the check that cannot be bypassed belongs inside send_to_github_api().
+    /// The default allows all requests.
+    fn authorize_github_request(&self, _github_request: &GitHubRequest) -> ResultWithGitHubLibError<()> {
+        Ok(())
+    }
+
+    /// Is this client only planning the requests, like the DryRunGitHubClient
+    ///
+    /// In the dry run the library does not change local files and does not run git commands that change the repository.
+    fn is_dry_run(&self) -> bool {
+        false
+    }
+
     /// Upload to github
     ///
     /// This function encapsulates the secret API secret_token.
@@ -32,215 +109,535 @@ pub trait SendToGitHubApi {
     fn upload_to_github(&self, req: reqwest::RequestBuilder) -> impl std::future::Future<Output = serde_json::Value> + Send;
 }
 
+/// Get github_owner from the repository URL in Cargo.toml or return an error
+///
+/// The second fragment of the URL `https://github.com/github_owner/crate_name` is the github_owner.
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn cargo_toml_github_owner(cargo_toml: &CargoTomlPackageMetadata) -> ResultWithGitHubLibError<String> {
+    cargo_toml
+        .repository
+        .as_deref()
+        .and_then(|repository| repository.trim_start_matches("https://").split('/').nth(1))
+        .filter(|github_owner| !github_owner.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| GitHubLibError::CargoTomlError("Element Repository in Cargo.toml does not contain the github_owner!".to_string()))
+}
+
+/// Get description from Cargo.toml or return an error
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn cargo_toml_description(cargo_toml: &CargoTomlPackageMetadata) -> ResultWithGitHubLibError<String> {
+    cargo_toml
+        .description
+        .clone()
+        .ok_or_else(|| GitHubLibError::CargoTomlError("Element Description in Cargo.toml does not exist!".to_string()))
+}
+
+/// Run a git command and return the stdout or an error
+fn git_command_output(args: &[&str]) -> ResultWithGitHubLibError<String> {
+    let command = format!("git {}", args.join(" "));
+    let output = std::process::Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| GitHubLibError::GitCommandError {
+            command: command.clone(),
+            message: e.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(GitHubLibError::GitCommandError {
+            command,
+            message: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// The default branch of the origin remote or None if the remote has no commits
+///
+/// git ls-remote --symref returns the line `ref: refs/heads/main\tHEAD` for the HEAD of the remote.
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn try_git_remote_default_branch() -> ResultWithGitHubLibError<Option<String>> {
+    let output = git_command_output(&["ls-remote", "--symref", "origin", "HEAD"])?;
+    Ok(output
+        .lines()
+        .find_map(|line| line.strip_prefix("ref: refs/heads/"))
+        .and_then(|line| line.split('\t').next())
+        .map(str::to_string))
+}
+
+/// Run the git command that changes the repository, in the dry run only print it
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn run_git_command(is_dry_run: bool, args: &[&str]) -> ResultWithGitHubLibError<()> {
+    if is_dry_run {
+        println!("    {YELLOW}Dry run: git {}{RESET}", args.join(" "));
+    } else {
+        git_command_output(args)?;
+    }
+    Ok(())
+}
+
 /// Has git remote
 pub fn git_has_remote() -> bool {
+    try_git_has_remote().unwrap_or_else(|e| panic!("{RED}{e}{RESET}"))
+}
+
+/// Has git remote, returns error instead of panic
+pub fn try_git_has_remote() -> ResultWithGitHubLibError<bool> {
     // git remote returns only "origin" if exists or nothing if it does not exist
-    let output = std::process::Command::new("git").arg("remote").output().unwrap();
+    let output = match git_command_output(&["remote"]) {
+        // outside of a git repository there is no remote
+        Err(GitHubLibError::GitCommandError { message, .. }) if message.contains("not a git repository") => return Ok(false),
+        output => output?,
+    };
     // return
-    String::from_utf8(output.stdout).unwrap() != ""
+    Ok(!output.is_empty())
 }
 
 /// Has git upstream
 pub fn git_has_upstream() -> bool {
+    try_git_has_upstream().unwrap_or_else(|e| panic!("{RED}{e}{RESET}"))
+}
+
+/// Has git upstream, returns error instead of panic
+pub fn try_git_has_upstream() -> ResultWithGitHubLibError<bool> {
     // git branch -vv returns upstream branches in angle brackets []
-    let output = std::process::Command::new("git").arg("branch").arg("-vv").output().unwrap();
+    let output = match git_command_output(&["branch", "-vv"]) {
+        // outside of a git repository there is no upstream
+        Err(GitHubLibError::GitCommandError { message, .. }) if message.contains("not a git repository") => return Ok(false),
+        output => output?,
+    };
     // return
-    String::from_utf8(output.stdout).unwrap().contains("[")
+    Ok(output.contains("["))
 }
 
 /// Interactive ask to create a new remote GitHub repository
 ///
 /// Use a function pointer to send_to_github_api() to avoid passing the secret_token.
+#[cfg(all(feature = "blocking", feature = "interactive"))]
 pub fn new_remote_github_repository(github_client: &impl SendToGitHubApi) -> Option<()> {
+    try_new_remote_github_repository(github_client).unwrap_or_else(|e| panic!("{RED}{e}{RESET}"))
+}
+
+/// Interactive ask to create a new remote GitHub repository, returns error instead of panic
+///
+/// Use a function pointer to send_to_github_api() to avoid passing the secret_token.
+/// Returns Ok(None) if the user does not want to create the repository.
+#[cfg(all(feature = "blocking", feature = "interactive"))]
+pub fn try_new_remote_github_repository(github_client: &impl SendToGitHubApi) -> ResultWithGitHubLibError<Option<()>> {
+    try_new_remote_github_repository_with_prompter(github_client, &InquirePrompter)
+}
+
+/// Ask the Prompter to create a new remote GitHub repository, returns error instead of panic
+///
+/// In CI and scripted bootstrap use the FixedAnswerPrompter instead of asking in the terminal.
+/// The settings of the new repository are from the `[package.metadata.github]` table in Cargo.toml.
+/// Returns Ok(None) if the answer is no.
+#[cfg(feature = "blocking")]
+pub fn try_new_remote_github_repository_with_prompter(
+    github_client: &impl SendToGitHubApi,
+    prompter: &impl Prompter,
+) -> ResultWithGitHubLibError<Option<()>> {
+    let new_repository_options = NewRepositoryOptions::from_github_metadata(&cargo_toml_github_metadata()?);
+    try_new_remote_github_repository_with_options(github_client, prompter, &new_repository_options)
+}
+
+/// Ask the Prompter to create a new remote GitHub repository with options, returns error instead of panic
+///
+/// The options set the visibility, template and merge strategies of the new repository.
+/// Returns Ok(None) if the answer is no.
+#[cfg(feature = "blocking")]
+pub fn try_new_remote_github_repository_with_options(
+    github_client: &impl SendToGitHubApi,
+    prompter: &impl Prompter,
+    new_repository_options: &NewRepositoryOptions,
+) -> ResultWithGitHubLibError<Option<()>> {
+    block_on(new_remote_github_repository_async(
+        &BlockingGitHubClient::new(github_client),
+        prompter,
+        new_repository_options,
+    ))
+}
+
+/// Ask the Prompter to create a new remote GitHub repository with options, async
+///
+/// Returns Ok(None) if the answer is no.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn new_remote_github_repository_async(
+    github_client: &impl SendToGitHubApiAsync,
+    prompter: &impl Prompter,
+    new_repository_options: &NewRepositoryOptions,
+) -> ResultWithGitHubLibError<Option<()>> {
     // early error if Repository contains the placeholder "github_owner" or does not contain the true github_owner
-    let cargo_toml = cl::CargoToml::read();
-    let package_name = cargo_toml.package_name();
+    let cargo_toml = cargo_toml_package_metadata()?;
+    let package_name = cargo_toml.name.clone();
     // the second fragment of URL can be the github_owner (authenticated_user) or organization
-    let github_owner_or_organization = cargo_toml
-        .github_owner()
-        .unwrap_or_else(|| panic!("{RED}ERROR: Element Repository in Cargo.toml does not contain the github_owner!{RESET}"));
+    let github_owner_or_organization = cargo_toml_github_owner(&cargo_toml)?;
     if github_owner_or_organization == "github_owner" {
-        panic!("{RED}Error: The placeholder 'github_owner' in Cargo.toml/repository is not changed to the real github_owner or GitHub Organization.{RESET}")
+        return Err(GitHubLibError::CargoTomlError(
+            "The placeholder 'github_owner' in Cargo.toml/repository is not changed to the real github_owner or GitHub Organization."
+                .to_string(),
+        ));
     }
 
     // get authenticated user from Github
-    let json_value = github_client.send_to_github_api(github_api_get_authenticated_user());
-    let Some(authenticated_user_login) = json_value.get("login") else {
-        panic!("{RED}ERROR: Unrecognized Authenticated on GitHub from secret_token.{RESET}");
-    };
-    let authenticated_user_login = authenticated_user_login.as_str().unwrap();
+    let authenticated_user: User = send_to_github_api_as_async(github_client, github_api_get_authenticated_user_async()).await?;
+    let authenticated_user_login = authenticated_user.login;
 
     if github_owner_or_organization == authenticated_user_login {
         // this repository is a User Repository
+        if new_repository_options.visibility == RepositoryVisibility::Internal {
+            return Err(GitHubLibError::ErrorFromString(
+                "Only an organization repository can have the visibility internal.".to_string(),
+            ));
+        }
     } else {
         // check if it is a GitHub Organization
-        let json_value = github_client.send_to_github_api(github_api_get_organization(&github_owner_or_organization));
-        let Some(_organization_login) = json_value.get("login") else {
-            panic!("{RED}ERROR: Unrecognized Organization on GitHub: {github_owner_or_organization}.{RESET}");
-        };
+        let _organization: Organization =
+            send_to_github_api_as_async(github_client, github_api_get_organization_async(&github_owner_or_organization)).await?;
     }
 
-    if !git_has_remote() {
-        let description = cargo_toml
-            .package_description()
-            .unwrap_or_else(|| panic!("{RED}ERROR: Element Description in Cargo.toml does not exist!{RESET}"));
+    // the default branch of the repository created now
+    let mut new_repository_default_branch = None;
+    if !try_git_has_remote()? {
+        let description = cargo_toml_description(&cargo_toml)?;
 
-        // ask interactive
+        // ask interactive or the scripted answer
         println!("    {BLUE}This project does not have a remote GitHub repository.{RESET}");
-        let answer = inquire::Text::new(&format!("{BLUE}Do you want to create a new remote GitHub repository? (y/n){RESET}"))
-            .prompt()
-            .unwrap();
-        if answer.to_lowercase() != "y" {
+        if !prompter.confirm("Do you want to create a new remote GitHub repository?")? {
             // early exit
-            return None;
+            return Ok(None);
         }
-        // continue if answer is "y"
+        // continue if answer is yes
 
-        let json_value = if github_owner_or_organization == authenticated_user_login {
-            // new User repository
-            let json_value = github_client.send_to_github_api(github_api_user_repository_new(&github_owner_or_organization, &package_name, &description));
-            // early exit on error
-            if let Some(error_message) = json_value.get("message") {
-                eprintln!("{RED}{error_message}{RESET}");
-                if let Some(errors) = json_value.get("errors") {
-                    let errors = errors.as_array().unwrap();
-                    for error in errors.iter() {
-                        if let Some(code) = error.get("message") {
-                            eprintln!("{RED}{code}{RESET}");
-                        }
-                    }
-                }
-                panic!("{RED}Call to GitHub API github_api_user_repository_new returned an error.{RESET}")
+        let repository: Repository = if let Some(template) = &new_repository_options.template {
+            // new repository from the template repository, for the user or the organization
+            if new_repository_options.visibility == RepositoryVisibility::Internal {
+                return Err(GitHubLibError::ErrorFromString(
+                    "A repository from a template can only be public or private.".to_string(),
+                ));
             }
-            json_value
+            send_to_github_api_as_async(
+                github_client,
+                github_api_create_repository_from_template_async(
+                    template,
+                    &github_owner_or_organization,
+                    &package_name,
+                    &description,
+                    new_repository_options.visibility == RepositoryVisibility::Private,
+                ),
+            )
+            .await?
+        } else if github_owner_or_organization == authenticated_user_login {
+            // new User repository
+            send_to_github_api_as_async(
+                github_client,
+                github_api_user_repository_new_with_options_async(
+                    &github_owner_or_organization,
+                    &package_name,
+                    &description,
+                    new_repository_options,
+                ),
+            )
+            .await?
         } else {
             // new Organization repository
-            let json_value = github_client.send_to_github_api(github_api_organization_repository_new(&github_owner_or_organization, &package_name, &description));
-            // early exit on error
-            if let Some(error_message) = json_value.get("message") {
-                eprintln!("{RED}{error_message}{RESET}");
-                if let Some(errors) = json_value.get("errors") {
-                    let errors = errors.as_array().unwrap();
-                    for error in errors.iter() {
-                        if let Some(code) = error.get("message") {
-                            eprintln!("{RED}{code}{RESET}");
-                        }
-                    }
-                }
-                panic!("{RED}Call to GitHub API github_api_organization_repository_new returned an error.{RESET}")
-            }
-            json_value
+            send_to_github_api_as_async(
+                github_client,
+                github_api_organization_repository_new_with_options_async(
+                    &github_owner_or_organization,
+                    &package_name,
+                    &description,
+                    new_repository_options,
+                ),
+            )
+            .await?
         };
 
-        // get just the name, description and html_url from json
-        println!("{YELLOW}name: {}{RESET}", json_value.get("name").unwrap().as_str().unwrap());
-        println!("{YELLOW}description: {}{RESET}", json_value.get("description").unwrap().as_str().unwrap());
-        let repo_html_url = json_value.get("html_url").unwrap().as_str().unwrap().to_string();
-        println!("{YELLOW}url: {}{RESET}", &repo_html_url);
+        new_repository_default_branch = repository.default_branch.clone();
+        // get just the name, description and html_url
+        println!("{YELLOW}name: {}{RESET}", repository.name);
+        println!("{YELLOW}description: {}{RESET}", repository.description.unwrap_or_default());
+        println!("{YELLOW}url: {}{RESET}", repository.html_url.unwrap_or_default());
 
         // add this GitHub repository to origin remote over SSH (use sshadd for passphrase)
-        cl::ShellCommandLimitedDoubleQuotesSanitizer::new(r#"git remote add origin "git@github.com:{github_owner_or_organization}/{name}.git" "#)
-            .unwrap()
+        let git_command_error = |e: String| GitHubLibError::GitCommandError {
+            command: "git remote add origin".to_string(),
+            message: e,
+        };
+        if github_client.is_dry_run() {
+            println!(
+                "    {YELLOW}Dry run: git remote add origin git@{}:{github_owner_or_organization}/{package_name}.git{RESET}",
+                github_endpoint().git_ssh_host
+            );
+        } else {
+            cl::ShellCommandLimitedDoubleQuotesSanitizer::new(
+                r#"git remote add origin "git@{git_ssh_host}:{github_owner_or_organization}/{name}.git" "#,
+            )
+            .map_err(|e| git_command_error(e.to_string()))?
+            .arg("{git_ssh_host}", &github_endpoint().git_ssh_host)
+            .map_err(|e| git_command_error(e.to_string()))?
             .arg("{github_owner_or_organization}", &github_owner_or_organization)
-            .unwrap()
+            .map_err(|e| git_command_error(e.to_string()))?
             .arg("{name}", &package_name)
-            .unwrap()
+            .map_err(|e| git_command_error(e.to_string()))?
             .run()
-            .unwrap();
+            .map_err(|e| git_command_error(e.to_string()))?;
+        }
     }
 
-    if !git_has_upstream() {
-        cl::run_shell_command("git push -u origin main").unwrap_or_else(|e| panic!("{e}"));
+    if !try_git_has_upstream()? {
+        // the template and auto_init create commits on the remote, the local commits go on top of them
+        let remote_default_branch = if github_client.is_dry_run() {
+            (new_repository_options.template.is_some() || new_repository_options.auto_init)
+                .then(|| new_repository_default_branch.clone().unwrap_or_else(|| "main".to_string()))
+        } else {
+            try_git_remote_default_branch()?
+        };
+        if let Some(remote_default_branch) = &remote_default_branch {
+            run_git_command(
+                github_client.is_dry_run(),
+                &[
+                    "pull",
+                    "--no-rebase",
+                    "--no-edit",
+                    "--allow-unrelated-histories",
+                    "origin",
+                    remote_default_branch,
+                ],
+            )?;
+        }
+        // the local branch is pushed to the default_branch from Cargo.toml or to the default branch of the remote
+        let branch = new_repository_options
+            .default_branch
+            .clone()
+            .or(remote_default_branch.clone())
+            .or(new_repository_default_branch)
+            .unwrap_or_else(|| "main".to_string());
+        run_git_command(github_client.is_dry_run(), &["push", "-u", "origin", &format!("HEAD:{branch}")])?;
+        // the first pushed branch becomes the default branch only if the remote had no commits
+        if remote_default_branch.is_some_and(|remote_default_branch| remote_default_branch != branch) {
+            let _repository: Repository = send_to_github_api_as_async(
+                github_client,
+                github_api_update_repository_async(
+                    &github_owner_or_organization,
+                    &package_name,
+                    &UpdateRepository::new().default_branch(&branch),
+                ),
+            )
+            .await?;
+        }
 
         // the docs pages are created with a GitHub action
-        let _json = github_client.send_to_github_api(github_api_create_a_github_pages_site(&github_owner_or_organization, &package_name));
+        let _response = send_to_github_api_with_retry_async(
+            github_client,
+            github_api_create_a_github_pages_site_with_source_async(
+                &github_owner_or_organization,
+                &package_name,
+                &new_repository_options.pages,
+            ),
+        )
+        .await?;
     }
 
-    Some(())
+    Ok(Some(()))
 }
 
-/// Check and modify the description and topics on Github
+/// Check and modify the description, topics and settings on Github
 ///
 /// The words topics, keywords and tags all mean the same concept.
 /// In cargo.toml we have keywords.
 /// In README.md I want to have badges for tags
 /// In GitHub they are topics.
 /// Topic must be only one word: lowercase letters, hyphens(-) or numbers, less then 35 characters.
-/// I want to avoid GitHub API at every git push. I will store the old description and topics
+/// The categories from Cargo.toml are also topics.
+/// The homepage on GitHub is the homepage or the documentation from Cargo.toml.
+/// The other settings come from the `[package.metadata.github]` table in Cargo.toml.
+/// Every changed field is printed.
+/// I want to avoid GitHub API at every git push. I will store the old metadata
 /// in the file automation_tasks_rs/.old_metadata.json
 /// So I can compare first locally and only when they differ call the Github API.
+#[cfg(feature = "blocking")]
 pub fn description_and_topics_to_github(github_client: &impl SendToGitHubApi) {
-    let cargo_toml = cl::CargoToml::read();
-    let repo_name = cargo_toml.package_name();
-    let github_owner_or_organization = cargo_toml.github_owner().unwrap();
-    let description = cargo_toml.package_description().unwrap();
-    let keywords = cargo_toml.package_keywords();
+    try_description_and_topics_to_github(github_client).unwrap_or_else(|e| panic!("{RED}{e}{RESET}"));
+}
+
+/// Check and modify the description, topics and settings on Github, returns the changed fields or error instead of panic
+///
+/// Look at the description_and_topics_to_github() for details.
+#[cfg(feature = "blocking")]
+pub fn try_description_and_topics_to_github(
+    github_client: &impl SendToGitHubApi,
+) -> ResultWithGitHubLibError<Vec<RepositoryMetadataChange>> {
+    block_on(description_and_topics_to_github_async(&BlockingGitHubClient::new(github_client)))
+}
+
+/// Check and modify the description, topics and settings on Github, async
+///
+/// Look at the description_and_topics_to_github() for details.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn description_and_topics_to_github_async(
+    github_client: &impl SendToGitHubApiAsync,
+) -> ResultWithGitHubLibError<Vec<RepositoryMetadataChange>> {
+    let cargo_toml = cargo_toml_package_metadata()?;
+    let repo_name = cargo_toml.name.clone();
+    let github_owner_or_organization = cargo_toml_github_owner(&cargo_toml)?;
+    let description = cargo_toml_description(&cargo_toml)?;
+    let keywords = cargo_toml.keywords;
+    let categories = cargo_toml.categories;
+    let github_metadata = cargo_toml.github_metadata;
+    let keywords_and_categories: Vec<String> = keywords
+        .iter()
+        .cloned()
+        .chain(categories.iter().map(|category| category_to_topic(category)))
+        .collect();
+    let topics = github_metadata.topics_with_keywords(&keywords_and_categories);
 
     #[derive(serde::Serialize, serde::Deserialize)]
     struct OldMetadata {
         old_description: String,
         old_keywords: Vec<String>,
+        // the files written before the metadata sync have no old_categories and old_github_metadata
+        #[serde(default)]
+        old_categories: Vec<String>,
+        #[serde(default)]
+        old_github_metadata: GitHubMetadata,
     }
 
     // read data from automation_tasks_rs/.old_metadata.json
     let mut is_old_metadata_different = true;
     if let Ok(old_metadata) = std::fs::read_to_string("automation_tasks_rs/.old_metadata.json") {
         if let Ok(old_metadata) = serde_json::from_str::<OldMetadata>(&old_metadata) {
-            if old_metadata.old_description == description && old_metadata.old_keywords == keywords {
+            if old_metadata.old_description == description
+                && old_metadata.old_keywords == keywords
+                && old_metadata.old_categories == categories
+                && old_metadata.old_github_metadata == github_metadata
+            {
                 is_old_metadata_different = false;
             }
         }
     }
 
+    let mut changes: Vec<RepositoryMetadataChange> = vec![];
     if is_old_metadata_different {
         // get data from GitHub
-        let json = github_client.send_to_github_api(github_api_get_repository(&github_owner_or_organization, &repo_name));
+        let repository: Repository = send_to_github_api_as_async(
+            github_client,
+            github_api_get_repository_async(&github_owner_or_organization, &repo_name),
+        )
+        .await?;
 
-        // get just the description and topis from json
-        let gh_description = json.get("description").unwrap().as_str().unwrap();
-        let gh_topics = json.get("topics").unwrap().as_array().unwrap();
-        let gh_topics: Vec<String> = gh_topics.into_iter().map(|value| value.as_str().unwrap().to_string()).collect();
-
-        // are description and topics both equal?
+        // the description is null if it was never set
+        let gh_description = repository.description.clone().unwrap_or_default();
         if gh_description != description {
-            let _json = github_client.send_to_github_api(github_api_update_description(&github_owner_or_organization, &repo_name, &description));
+            let _repository: Repository = send_to_github_api_as_async(
+                github_client,
+                github_api_update_description_async(&github_owner_or_organization, &repo_name, &description),
+            )
+            .await?;
+            changes.push(RepositoryMetadataChange {
+                field: "description".to_string(),
+                old_value: serde_json::json!(gh_description),
+                new_value: serde_json::json!(description),
+            });
         }
 
         // all elements must be equal, but not necessary in the same order
-        let topics_is_equal = if gh_topics.len() == keywords.len() {
-            let mut elements_is_equal = true;
-            'outer: for x in gh_topics.iter() {
-                let mut has_element = false;
-                'inner: for y in keywords.iter() {
-                    if y == x {
-                        has_element = true;
-                        break 'inner;
-                    }
-                }
-                if !has_element {
-                    elements_is_equal = false;
-                    break 'outer;
-                }
-            }
-            elements_is_equal
-        } else {
-            false
-        };
-
+        let gh_topics = &repository.topics;
+        let topics_is_equal = gh_topics.len() == topics.len() && gh_topics.iter().all(|topic| topics.contains(topic));
         if !topics_is_equal {
-            let _json = github_client.send_to_github_api(github_api_replace_all_topics(&github_owner_or_organization, &repo_name, &keywords));
-            // write into automation_tasks_rs/.old_metadata.json file
+            let _topics: Topics = send_to_github_api_as_async(
+                github_client,
+                github_api_replace_all_topics_async(&github_owner_or_organization, &repo_name, &topics),
+            )
+            .await?;
+            changes.push(RepositoryMetadataChange {
+                field: "topics".to_string(),
+                old_value: serde_json::json!(gh_topics),
+                new_value: serde_json::json!(topics),
+            });
+        }
+
+        // the homepage and the settings from the [package.metadata.github] table
+        let update_repository = github_metadata.repository_changes(&repository);
+        if !update_repository.is_empty() {
+            let _repository: Repository = send_to_github_api_as_async(
+                github_client,
+                github_api_update_repository_async(&github_owner_or_organization, &repo_name, &update_repository),
+            )
+            .await?;
+            changes.extend(repository_metadata_changes(&update_repository, &repository));
+        }
+
+        for change in changes.iter() {
+            println!("    {YELLOW}GitHub {change}{RESET}");
+        }
+
+        // write into automation_tasks_rs/.old_metadata.json file, but not in the dry run
+        if !github_client.is_dry_run() {
             let old_metadata = OldMetadata {
                 old_description: description,
                 old_keywords: keywords,
+                old_categories: categories,
+                old_github_metadata: github_metadata,
             };
-            std::fs::write("automation_tasks_rs/.old_metadata.json", serde_json::to_string_pretty(&old_metadata).unwrap()).unwrap();
+            std::fs::write(
+                "automation_tasks_rs/.old_metadata.json",
+                serde_json::to_string_pretty(&old_metadata)?,
+            )?;
         }
     }
+    Ok(changes)
+}
+
+/// Create a request builder with the headers common to all GitHub API requests
+///
+/// The url is already constructed with the base url from github_endpoint().
+/// The headers come from github_headers().
+/// The shared http_client() keeps the connection pool and the configuration for all requests.
+#[cfg(feature = "blocking")]
+pub(crate) fn github_api_request_builder(method: reqwest::Method, url: &str) -> reqwest::blocking::RequestBuilder {
+    let github_headers = github_headers();
+    let mut req = http_client()
+        .request(method, url)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", &github_headers.api_version)
+        .header("User-Agent", &github_headers.user_agent);
+    for (name, value) in &github_headers.extra_headers {
+        req = req.header(name, value);
+    }
+    req
+}
+
+/// Create an async request builder with the headers common to all GitHub API requests
+///
+/// Look at github_api_request_builder() for details.
+pub(crate) fn github_api_request_builder_async(method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+    let github_headers = github_headers();
+    let mut req = async_http_client()
+        .request(method, url)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", &github_headers.api_version)
+        .header("User-Agent", &github_headers.user_agent);
+    for (name, value) in &github_headers.extra_headers {
+        req = req.header(name, value);
+    }
+    req
 }
 
 /// GitHub api get authenticated user
+#[cfg(feature = "blocking")]
 pub fn github_api_get_authenticated_user() -> reqwest::blocking::RequestBuilder {
+    get_authenticated_user_request().request_builder()
+}
+
+/// GitHub api get authenticated user, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_get_authenticated_user_async() -> reqwest::RequestBuilder {
+    get_authenticated_user_request().async_request_builder()
+}
+
+/// Describe the request for github_api_get_authenticated_user()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn get_authenticated_user_request() -> GitHubRequest {
     /*
         https://docs.github.com/en/rest/users/users?apiVersion=2022-11-28#get-the-authenticated-user
 
@@ -255,17 +652,26 @@ pub fn github_api_get_authenticated_user() -> reqwest::blocking::RequestBuilder
         "id": 1,
         }
     */
-    let repos_url = format!("https://api.github.com/user");
-    // return
-    reqwest::blocking::Client::new()
-        .get(repos_url.as_str())
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/user");
+    GitHubRequest::from_url(reqwest::Method::GET, &repos_url, None)
 }
 
 /// GitHub api get organization
+#[cfg(feature = "blocking")]
 pub fn github_api_get_organization(organization: &str) -> reqwest::blocking::RequestBuilder {
+    get_organization_request(organization).request_builder()
+}
+
+/// GitHub api get organization, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_get_organization_async(organization: &str) -> reqwest::RequestBuilder {
+    get_organization_request(organization).async_request_builder()
+}
+
+/// Describe the request for github_api_get_organization()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn get_organization_request(organization: &str) -> GitHubRequest {
     /*
         https://docs.github.com/en/rest/orgs/orgs?apiVersion=2022-11-28#get-an-organization
 
@@ -280,17 +686,26 @@ pub fn github_api_get_organization(organization: &str) -> reqwest::blocking::Req
         "id": 1,
         }
     */
-    let repos_url = format!("https://api.github.com/orgs/{organization}");
-    // return
-    reqwest::blocking::Client::new()
-        .get(repos_url.as_str())
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/orgs/{organization}");
+    GitHubRequest::from_url(reqwest::Method::GET, &repos_url, None)
 }
 
 /// GitHub api get repository
+#[cfg(feature = "blocking")]
 pub fn github_api_get_repository(github_owner_or_organization: &str, repo_name: &str) -> reqwest::blocking::RequestBuilder {
+    get_repository_request(github_owner_or_organization, repo_name).request_builder()
+}
+
+/// GitHub api get repository, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_get_repository_async(github_owner_or_organization: &str, repo_name: &str) -> reqwest::RequestBuilder {
+    get_repository_request(github_owner_or_organization, repo_name).async_request_builder()
+}
+
+/// Describe the request for github_api_get_repository()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn get_repository_request(github_owner_or_organization: &str, repo_name: &str) -> GitHubRequest {
     /*
         https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#get-a-repository
 
@@ -300,18 +715,342 @@ pub fn github_api_get_repository(github_owner_or_organization: &str, repo_name:
         -H "X-GitHub-Api-Version: 2022-11-28" \
         https://api.github.com/repos/github_owner/REPO
     */
-    let repos_url = format!("https://api.github.com/repos/{github_owner_or_organization}/{repo_name}");
-    // return
-    reqwest::blocking::Client::new()
-        .get(repos_url.as_str())
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo_name}");
+    GitHubRequest::from_url(reqwest::Method::GET, &repos_url, None)
+}
+
+/// Visibility of the repository
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RepositoryVisibility {
+    Public,
+    Private,
+    /// only for organization repositories in GitHub Enterprise
+    Internal,
+}
+
+impl RepositoryVisibility {
+    /// The string value for GitHub API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepositoryVisibility::Public => "public",
+            RepositoryVisibility::Private => "private",
+            RepositoryVisibility::Internal => "internal",
+        }
+    }
+}
+
+/// Template repository for the new repository
+///
+/// ```rust ignore
+/// let template = cgl::RepositoryTemplate::new("automation-tasks-rs", "crate_template").include_all_branches(true);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryTemplate {
+    pub template_owner: String,
+    pub template_repo: String,
+    /// copy all the branches, not only the default branch
+    pub include_all_branches: bool,
+}
+
+impl RepositoryTemplate {
+    /// Template repository with only the default branch
+    pub fn new(template_owner: &str, template_repo: &str) -> Self {
+        RepositoryTemplate {
+            template_owner: template_owner.to_string(),
+            template_repo: template_repo.to_string(),
+            include_all_branches: false,
+        }
+    }
+
+    /// Copy all the branches of the template
+    pub fn include_all_branches(mut self, include_all_branches: bool) -> Self {
+        self.include_all_branches = include_all_branches;
+        self
+    }
+}
+
+/// Options for the new repository
+///
+/// The default is a public repository with issues and discussions, like it was always.
+/// ```rust ignore
+/// let new_repository_options = cgl::NewRepositoryOptions::new().visibility(cgl::RepositoryVisibility::Private).delete_branch_on_merge(true);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewRepositoryOptions {
+    pub visibility: RepositoryVisibility,
+    /// None means the GitHub pages of the repository
+    pub homepage: Option<String>,
+    pub has_issues: bool,
+    pub has_projects: bool,
+    pub has_wiki: bool,
+    pub has_discussions: bool,
+    pub is_template: bool,
+    /// GitHub creates the initial commit, the local commits are merged on top of it before the first push
+    pub auto_init: bool,
+    /// name of the .gitignore template like `Rust`, only with auto_init
+    pub gitignore_template: Option<String>,
+    /// keyword of the license like `mit`, only with auto_init
+    pub license_template: Option<String>,
+    /// None means the GitHub default for the merge strategies
+    pub allow_squash_merge: Option<bool>,
+    pub allow_merge_commit: Option<bool>,
+    pub allow_rebase_merge: Option<bool>,
+    pub delete_branch_on_merge: bool,
+    /// generate the new repository from this template repository
+    ///
+    /// GitHub copies the files of the template, only the visibility and description apply.
+    pub template: Option<RepositoryTemplate>,
+    /// the source of the GitHub pages created after the first push
+    pub pages: PagesSource,
+    /// the branch of the first push, None means the default branch of the remote
+    pub default_branch: Option<String>,
+}
+
+impl Default for NewRepositoryOptions {
+    fn default() -> Self {
+        NewRepositoryOptions {
+            visibility: RepositoryVisibility::Public,
+            homepage: None,
+            has_issues: true,
+            has_projects: false,
+            has_wiki: false,
+            has_discussions: true,
+            is_template: false,
+            auto_init: false,
+            gitignore_template: None,
+            license_template: None,
+            allow_squash_merge: None,
+            allow_merge_commit: None,
+            allow_rebase_merge: None,
+            delete_branch_on_merge: false,
+            template: None,
+            pages: PagesSource::default(),
+            default_branch: None,
+        }
+    }
+}
+
+impl NewRepositoryOptions {
+    /// Public repository with issues and discussions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The settings from the `[package.metadata.github]` table in Cargo.toml
+    ///
+    /// The settings that are not in the table keep the default.
+    pub fn from_github_metadata(github_metadata: &GitHubMetadata) -> Self {
+        let default = Self::default();
+        NewRepositoryOptions {
+            visibility: github_metadata.visibility.unwrap_or(default.visibility),
+            homepage: github_metadata.homepage.clone(),
+            has_issues: github_metadata.has_issues.unwrap_or(default.has_issues),
+            has_projects: github_metadata.has_projects.unwrap_or(default.has_projects),
+            has_wiki: github_metadata.has_wiki.unwrap_or(default.has_wiki),
+            has_discussions: github_metadata.has_discussions.unwrap_or(default.has_discussions),
+            allow_squash_merge: github_metadata.allow_squash_merge,
+            allow_merge_commit: github_metadata.allow_merge_commit,
+            allow_rebase_merge: github_metadata.allow_rebase_merge,
+            delete_branch_on_merge: github_metadata.delete_branch_on_merge.unwrap_or(default.delete_branch_on_merge),
+            pages: github_metadata.pages.clone().unwrap_or(default.pages),
+            default_branch: github_metadata.default_branch.clone(),
+            ..default
+        }
+    }
+
+    /// Public, private or internal
+    pub fn visibility(mut self, visibility: RepositoryVisibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Homepage instead of the GitHub pages
+    pub fn homepage(mut self, homepage: &str) -> Self {
+        self.homepage = Some(homepage.to_string());
+        self
+    }
+
+    /// Enable issues
+    pub fn has_issues(mut self, has_issues: bool) -> Self {
+        self.has_issues = has_issues;
+        self
+    }
+
+    /// Enable projects
+    pub fn has_projects(mut self, has_projects: bool) -> Self {
+        self.has_projects = has_projects;
+        self
+    }
+
+    /// Enable wiki
+    pub fn has_wiki(mut self, has_wiki: bool) -> Self {
+        self.has_wiki = has_wiki;
+        self
+    }
+
+    /// Enable discussions
+    pub fn has_discussions(mut self, has_discussions: bool) -> Self {
+        self.has_discussions = has_discussions;
+        self
+    }
+
+    /// The repository is a template for new repositories
+    pub fn is_template(mut self, is_template: bool) -> Self {
+        self.is_template = is_template;
+        self
+    }
+
+    /// GitHub creates the initial commit with README.md
+    pub fn auto_init(mut self, auto_init: bool) -> Self {
+        self.auto_init = auto_init;
+        self
+    }
+
+    /// The .gitignore template for the initial commit
+    pub fn gitignore_template(mut self, gitignore_template: &str) -> Self {
+        self.gitignore_template = Some(gitignore_template.to_string());
+        self
+    }
+
+    /// The license template for the initial commit
+    pub fn license_template(mut self, license_template: &str) -> Self {
+        self.license_template = Some(license_template.to_string());
+        self
+    }
+
+    /// Allow squash merge of pull requests
+    pub fn allow_squash_merge(mut self, allow_squash_merge: bool) -> Self {
+        self.allow_squash_merge = Some(allow_squash_merge);
+        self
+    }
+
+    /// Allow merge commits of pull requests
+    pub fn allow_merge_commit(mut self, allow_merge_commit: bool) -> Self {
+        self.allow_merge_commit = Some(allow_merge_commit);
+        self
+    }
+
+    /// Allow rebase merge of pull requests
+    pub fn allow_rebase_merge(mut self, allow_rebase_merge: bool) -> Self {
+        self.allow_rebase_merge = Some(allow_rebase_merge);
+        self
+    }
+
+    /// Delete the head branch after the pull request is merged
+    pub fn delete_branch_on_merge(mut self, delete_branch_on_merge: bool) -> Self {
+        self.delete_branch_on_merge = delete_branch_on_merge;
+        self
+    }
+
+    /// The source of the GitHub pages
+    pub fn pages(mut self, pages: PagesSource) -> Self {
+        self.pages = pages;
+        self
+    }
+
+    /// The branch of the first push becomes the default branch
+    pub fn default_branch(mut self, default_branch: &str) -> Self {
+        self.default_branch = Some(default_branch.to_string());
+        self
+    }
+
+    /// Generate the new repository from the template repository
+    pub fn template(mut self, template: RepositoryTemplate) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    /// The json body for the new repository
+    ///
+    /// The user repository has only `private`, the organization repository has also `visibility`.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn to_json(&self, github_owner_or_organization: &str, name: &str, description: &str, is_organization: bool) -> serde_json::Value {
+        let homepage = match &self.homepage {
+            Some(homepage) => homepage.clone(),
+            None => format!("https://{github_owner_or_organization}.github.io/{name}"),
+        };
+        let mut body = serde_json::json!({
+            "name": name,
+            "description": description,
+            "homepage": homepage,
+            "private": self.visibility != RepositoryVisibility::Public,
+            "has_issues": self.has_issues,
+            "has_projects": self.has_projects,
+            "has_wiki": self.has_wiki,
+            "has_discussions": self.has_discussions,
+            "is_template": self.is_template,
+            "auto_init": self.auto_init,
+            "delete_branch_on_merge": self.delete_branch_on_merge,
+        });
+        if is_organization {
+            body["visibility"] = serde_json::json!(self.visibility.as_str());
+        }
+        if let Some(gitignore_template) = &self.gitignore_template {
+            body["gitignore_template"] = serde_json::json!(gitignore_template);
+        }
+        if let Some(license_template) = &self.license_template {
+            body["license_template"] = serde_json::json!(license_template);
+        }
+        if let Some(allow_squash_merge) = self.allow_squash_merge {
+            body["allow_squash_merge"] = serde_json::json!(allow_squash_merge);
+        }
+        if let Some(allow_merge_commit) = self.allow_merge_commit {
+            body["allow_merge_commit"] = serde_json::json!(allow_merge_commit);
+        }
+        if let Some(allow_rebase_merge) = self.allow_rebase_merge {
+            body["allow_rebase_merge"] = serde_json::json!(allow_rebase_merge);
+        }
+        body
+    }
 }
 
 /// Create a new github User repository
 /// TODO: slightly different API call for organization repository. How to distinguish user and organization?
+#[cfg(feature = "blocking")]
 pub fn github_api_user_repository_new(github_owner: &str, name: &str, description: &str) -> reqwest::blocking::RequestBuilder {
+    user_repository_new_request(github_owner, name, description, &NewRepositoryOptions::default()).request_builder()
+}
+
+/// Create a new github User repository, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+// the new repository flow uses the _with_options_async() variant, so only the async API exports this one
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+pub fn github_api_user_repository_new_async(github_owner: &str, name: &str, description: &str) -> reqwest::RequestBuilder {
+    user_repository_new_request(github_owner, name, description, &NewRepositoryOptions::default()).async_request_builder()
+}
+
+/// Create a new github User repository with options for visibility, template and merge strategies
+#[cfg(feature = "blocking")]
+pub fn github_api_user_repository_new_with_options(
+    github_owner: &str,
+    name: &str,
+    description: &str,
+    new_repository_options: &NewRepositoryOptions,
+) -> reqwest::blocking::RequestBuilder {
+    user_repository_new_request(github_owner, name, description, new_repository_options).request_builder()
+}
+
+/// Create a new github User repository with options for visibility, template and merge strategies, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_user_repository_new_with_options_async(
+    github_owner: &str,
+    name: &str,
+    description: &str,
+    new_repository_options: &NewRepositoryOptions,
+) -> reqwest::RequestBuilder {
+    user_repository_new_request(github_owner, name, description, new_repository_options).async_request_builder()
+}
+
+/// Describe the request for github_api_user_repository_new_with_options()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn user_repository_new_request(
+    github_owner: &str,
+    name: &str,
+    description: &str,
+    new_repository_options: &NewRepositoryOptions,
+) -> GitHubRequest {
     /*
     https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#create-a-repository-for-the-authenticated-user
 
@@ -336,33 +1075,59 @@ pub fn github_api_user_repository_new(github_owner: &str, name: &str, descriptio
     ...
     }
     */
-    let repos_url = format!("https://api.github.com/user/repos");
-    let body = serde_json::json!({
-        "name": name,
-        "description": description,
-        "homepage": format!("https://{github_owner}.github.io/{name}"),
-        "private":false,
-        "has_issues":true,
-        "has_projects":false,
-        "has_wiki":false,
-        // more settings...
-        "has_discussions" :true
-    });
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/user/repos");
+    let body = new_repository_options.to_json(github_owner, name, description, false);
     // Sadly there is no way in the API to set the settings: releases, packages and deployments
-    let body = body.to_string();
-
-    reqwest::blocking::Client::new()
-        .post(repos_url.as_str())
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
-        .body(body)
+    GitHubRequest::from_url(reqwest::Method::POST, &repos_url, Some(body))
 }
 
 /// Create a new github organization repository
+#[cfg(feature = "blocking")]
 pub fn github_api_organization_repository_new(organization: &str, name: &str, description: &str) -> reqwest::blocking::RequestBuilder {
+    organization_repository_new_request(organization, name, description, &NewRepositoryOptions::default()).request_builder()
+}
+
+/// Create a new github organization repository, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+// the new repository flow uses the _with_options_async() variant, so only the async API exports this one
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+pub fn github_api_organization_repository_new_async(organization: &str, name: &str, description: &str) -> reqwest::RequestBuilder {
+    organization_repository_new_request(organization, name, description, &NewRepositoryOptions::default()).async_request_builder()
+}
+
+/// Create a new github organization repository with options for visibility, template and merge strategies
+#[cfg(feature = "blocking")]
+pub fn github_api_organization_repository_new_with_options(
+    organization: &str,
+    name: &str,
+    description: &str,
+    new_repository_options: &NewRepositoryOptions,
+) -> reqwest::blocking::RequestBuilder {
+    organization_repository_new_request(organization, name, description, new_repository_options).request_builder()
+}
+
+/// Create a new github organization repository with options for visibility, template and merge strategies, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_organization_repository_new_with_options_async(
+    organization: &str,
+    name: &str,
+    description: &str,
+    new_repository_options: &NewRepositoryOptions,
+) -> reqwest::RequestBuilder {
+    organization_repository_new_request(organization, name, description, new_repository_options).async_request_builder()
+}
+
+/// Describe the request for github_api_organization_repository_new_with_options()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn organization_repository_new_request(
+    organization: &str,
+    name: &str,
+    description: &str,
+    new_repository_options: &NewRepositoryOptions,
+) -> GitHubRequest {
     /*
-    https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#create-a-repository-for-the-authenticated-user
+    https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#create-an-organization-repository
 
     Request like :
     curl -L \
@@ -376,9 +1141,11 @@ pub fn github_api_organization_repository_new(organization: &str, name: &str, de
         "description":"This is your first repository",
         "homepage":"https://github.com",
         "private":false,
+        "visibility":"public",
         "has_issues":true,
         "has_projects":true,
-        "has_wiki":true
+        "has_wiki":true,
+        "delete_branch_on_merge":true
     }'
 
     Response (short)
@@ -387,31 +1154,301 @@ pub fn github_api_organization_repository_new(organization: &str, name: &str, de
     ...
     }
     */
-    let repos_url = format!("https://api.github.com/orgs/{organization}/repos");
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/orgs/{organization}/repos");
+    let body = new_repository_options.to_json(organization, name, description, true);
+    // Sadly there is no way in the API to set the settings: releases, packages and deployments
+    GitHubRequest::from_url(reqwest::Method::POST, &repos_url, Some(body))
+}
+
+/// Create a new github repository from a template repository
+///
+/// The owner can be the authenticated user or an organization.
+#[cfg(feature = "blocking")]
+pub fn github_api_create_repository_from_template(
+    template: &RepositoryTemplate,
+    github_owner_or_organization: &str,
+    name: &str,
+    description: &str,
+    private: bool,
+) -> reqwest::blocking::RequestBuilder {
+    create_repository_from_template_request(template, github_owner_or_organization, name, description, private).request_builder()
+}
+
+/// Create a new github repository from a template repository, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_create_repository_from_template_async(
+    template: &RepositoryTemplate,
+    github_owner_or_organization: &str,
+    name: &str,
+    description: &str,
+    private: bool,
+) -> reqwest::RequestBuilder {
+    create_repository_from_template_request(template, github_owner_or_organization, name, description, private).async_request_builder()
+}
+
+/// Describe the request for github_api_create_repository_from_template()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn create_repository_from_template_request(
+    template: &RepositoryTemplate,
+    github_owner_or_organization: &str,
+    name: &str,
+    description: &str,
+    private: bool,
+) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#create-a-repository-using-a-template
+
+    Request like :
+    curl -L \
+    -X POST \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/TEMPLATE_OWNER/TEMPLATE_REPO/generate \
+    -d '{
+        "owner":"octocat",
+        "name":"Hello-World",
+        "description":"This is your first repository",
+        "include_all_branches":false,
+        "private":false
+    }'
+
+    Response (short)
+    {
+    "id": 1296269,
+    ...
+    }
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let generate_url = format!(
+        "{api_base_url}/repos/{}/{}/generate",
+        template.template_owner, template.template_repo
+    );
     let body = serde_json::json!({
+        "owner": github_owner_or_organization,
         "name": name,
         "description": description,
-        "homepage": format!("https://{organization}.github.io/{name}"),
-        "private":false,
-        "has_issues":true,
-        "has_projects":false,
-        "has_wiki":false,
-        // more settings...
-        "has_discussions" :true
+        "include_all_branches": template.include_all_branches,
+        "private": private,
     });
-    // Sadly there is no way in the API to set the settings: releases, packages and deployments
-    let body = body.to_string();
+    GitHubRequest::from_url(reqwest::Method::POST, &generate_url, Some(body))
+}
 
-    reqwest::blocking::Client::new()
-        .post(repos_url.as_str())
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
-        .body(body)
+/// Settings to update in an existing repository
+///
+/// Only the fields that are set will be sent to GitHub.
+/// ```rust ignore
+/// let update_repository = cgl::UpdateRepository::new().has_wiki(false).delete_branch_on_merge(true);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateRepository {
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub visibility: Option<RepositoryVisibility>,
+    pub default_branch: Option<String>,
+    pub has_issues: Option<bool>,
+    pub has_projects: Option<bool>,
+    pub has_wiki: Option<bool>,
+    pub has_discussions: Option<bool>,
+    pub allow_squash_merge: Option<bool>,
+    pub allow_merge_commit: Option<bool>,
+    pub allow_rebase_merge: Option<bool>,
+    pub delete_branch_on_merge: Option<bool>,
+}
+
+impl UpdateRepository {
+    /// Nothing to update yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// There is nothing to update
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The json body with only the fields that are set
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        let mut body = serde_json::Map::new();
+        let strings = [
+            ("description", &self.description),
+            ("homepage", &self.homepage),
+            ("default_branch", &self.default_branch),
+        ];
+        for (field, value) in strings {
+            if let Some(value) = value {
+                body.insert(field.to_string(), serde_json::json!(value));
+            }
+        }
+        if let Some(visibility) = self.visibility {
+            body.insert("visibility".to_string(), serde_json::json!(visibility.as_str()));
+        }
+        let bools = [
+            ("has_issues", self.has_issues),
+            ("has_projects", self.has_projects),
+            ("has_wiki", self.has_wiki),
+            ("has_discussions", self.has_discussions),
+            ("allow_squash_merge", self.allow_squash_merge),
+            ("allow_merge_commit", self.allow_merge_commit),
+            ("allow_rebase_merge", self.allow_rebase_merge),
+            ("delete_branch_on_merge", self.delete_branch_on_merge),
+        ];
+        for (field, value) in bools {
+            if let Some(value) = value {
+                body.insert(field.to_string(), serde_json::json!(value));
+            }
+        }
+        serde_json::Value::Object(body)
+    }
+
+    /// New description
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// New homepage
+    pub fn homepage(mut self, homepage: &str) -> Self {
+        self.homepage = Some(homepage.to_string());
+        self
+    }
+
+    /// Public, private or internal
+    pub fn visibility(mut self, visibility: RepositoryVisibility) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
+
+    /// The existing branch becomes the default branch
+    pub fn default_branch(mut self, default_branch: &str) -> Self {
+        self.default_branch = Some(default_branch.to_string());
+        self
+    }
+
+    /// Enable or disable issues
+    pub fn has_issues(mut self, has_issues: bool) -> Self {
+        self.has_issues = Some(has_issues);
+        self
+    }
+
+    /// Enable or disable projects
+    pub fn has_projects(mut self, has_projects: bool) -> Self {
+        self.has_projects = Some(has_projects);
+        self
+    }
+
+    /// Enable or disable wiki
+    pub fn has_wiki(mut self, has_wiki: bool) -> Self {
+        self.has_wiki = Some(has_wiki);
+        self
+    }
+
+    /// Enable or disable discussions
+    pub fn has_discussions(mut self, has_discussions: bool) -> Self {
+        self.has_discussions = Some(has_discussions);
+        self
+    }
+
+    /// Allow squash merge of pull requests
+    pub fn allow_squash_merge(mut self, allow_squash_merge: bool) -> Self {
+        self.allow_squash_merge = Some(allow_squash_merge);
+        self
+    }
+
+    /// Allow merge commits of pull requests
+    pub fn allow_merge_commit(mut self, allow_merge_commit: bool) -> Self {
+        self.allow_merge_commit = Some(allow_merge_commit);
+        self
+    }
+
+    /// Allow rebase merge of pull requests
+    pub fn allow_rebase_merge(mut self, allow_rebase_merge: bool) -> Self {
+        self.allow_rebase_merge = Some(allow_rebase_merge);
+        self
+    }
+
+    /// Delete the head branch after the pull request is merged
+    pub fn delete_branch_on_merge(mut self, delete_branch_on_merge: bool) -> Self {
+        self.delete_branch_on_merge = Some(delete_branch_on_merge);
+        self
+    }
+}
+
+/// GitHub API update the settings of a repository
+#[cfg(feature = "blocking")]
+pub fn github_api_update_repository(
+    github_owner_or_organization: &str,
+    repo_name: &str,
+    update_repository: &UpdateRepository,
+) -> reqwest::blocking::RequestBuilder {
+    update_repository_request(github_owner_or_organization, repo_name, update_repository).request_builder()
+}
+
+/// GitHub API update the settings of a repository, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_update_repository_async(
+    github_owner_or_organization: &str,
+    repo_name: &str,
+    update_repository: &UpdateRepository,
+) -> reqwest::RequestBuilder {
+    update_repository_request(github_owner_or_organization, repo_name, update_repository).async_request_builder()
+}
+
+/// Describe the request for github_api_update_repository()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn update_repository_request(github_owner_or_organization: &str, repo_name: &str, update_repository: &UpdateRepository) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#update-a-repository
+
+    curl -L \
+    -X PATCH \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/github_owner/REPO \
+    -d '{
+        "homepage":"https://github.com",
+        "visibility":"public",
+        "default_branch":"main",
+        "has_wiki":false,
+        "delete_branch_on_merge":true}'
+
+    Response (short)
+    {
+    "id": 1296269,
+    ...
+    }
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo_name}");
+    GitHubRequest::from_url(reqwest::Method::PATCH, &repos_url, Some(update_repository.to_json()))
 }
 
 /// GitHub api update description
-pub fn github_api_update_description(github_owner_or_organization: &str, repo_name: &str, description: &str) -> reqwest::blocking::RequestBuilder {
+#[cfg(feature = "blocking")]
+pub fn github_api_update_description(
+    github_owner_or_organization: &str,
+    repo_name: &str,
+    description: &str,
+) -> reqwest::blocking::RequestBuilder {
+    update_description_request(github_owner_or_organization, repo_name, description).request_builder()
+}
+
+/// GitHub api update description, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_update_description_async(
+    github_owner_or_organization: &str,
+    repo_name: &str,
+    description: &str,
+) -> reqwest::RequestBuilder {
+    update_description_request(github_owner_or_organization, repo_name, description).async_request_builder()
+}
+
+/// Describe the request for github_api_update_description()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn update_description_request(github_owner_or_organization: &str, repo_name: &str, description: &str) -> GitHubRequest {
     /*
     https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#update-a-repository
 
@@ -442,22 +1479,37 @@ pub fn github_api_update_description(github_owner_or_organization: &str, repo_na
     ...
     }
     */
-    let repos_url = format!("https://api.github.com/repos/{github_owner_or_organization}/{repo_name}");
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo_name}");
     let body = serde_json::json!({
         "description": description,
     });
-    let body = body.to_string();
-
-    reqwest::blocking::Client::new()
-        .patch(repos_url.as_str())
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
-        .body(body)
+    GitHubRequest::from_url(reqwest::Method::PATCH, &repos_url, Some(body))
 }
 
 /// GitHub API replace all topics
-pub fn github_api_replace_all_topics(github_owner_or_organization: &str, repo_name: &str, topics: &Vec<String>) -> reqwest::blocking::RequestBuilder {
+#[cfg(feature = "blocking")]
+pub fn github_api_replace_all_topics(
+    github_owner_or_organization: &str,
+    repo_name: &str,
+    topics: &Vec<String>,
+) -> reqwest::blocking::RequestBuilder {
+    replace_all_topics_request(github_owner_or_organization, repo_name, topics).request_builder()
+}
+
+/// GitHub API replace all topics, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_replace_all_topics_async(
+    github_owner_or_organization: &str,
+    repo_name: &str,
+    topics: &Vec<String>,
+) -> reqwest::RequestBuilder {
+    replace_all_topics_request(github_owner_or_organization, repo_name, topics).async_request_builder()
+}
+
+/// Describe the request for github_api_replace_all_topics()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn replace_all_topics_request(github_owner_or_organization: &str, repo_name: &str, topics: &Vec<String>) -> GitHubRequest {
     /*
     https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#replace-all-repository-topics
     curl -L \
@@ -468,22 +1520,51 @@ pub fn github_api_replace_all_topics(github_owner_or_organization: &str, repo_na
       https://api.github.com/repos/github_owner/REPO/topics \
       -d '{"names":["cat","atom","electron","api"]}'
      */
-    let repos_url = format!("https://api.github.com/repos/{github_owner_or_organization}/{repo_name}/topics");
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo_name}/topics");
     let body = serde_json::json!({
         "names": topics,
     });
-    let body = body.to_string();
-
-    reqwest::blocking::Client::new()
-        .put(repos_url.as_str())
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
-        .body(body)
+    GitHubRequest::from_url(reqwest::Method::PUT, &repos_url, Some(body))
 }
 
 /// GitHub API create-a-github-pages-site
+#[cfg(feature = "blocking")]
 pub fn github_api_create_a_github_pages_site(github_owner_or_organization: &str, repo_name: &str) -> reqwest::blocking::RequestBuilder {
+    create_a_github_pages_site_request(github_owner_or_organization, repo_name, &PagesSource::default()).request_builder()
+}
+
+/// GitHub API create-a-github-pages-site, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+// the new repository flow uses the _with_source_async() variant, so only the async API exports this one
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+pub fn github_api_create_a_github_pages_site_async(github_owner_or_organization: &str, repo_name: &str) -> reqwest::RequestBuilder {
+    create_a_github_pages_site_request(github_owner_or_organization, repo_name, &PagesSource::default()).async_request_builder()
+}
+
+/// GitHub API create-a-github-pages-site with the source branch and path
+#[cfg(feature = "blocking")]
+pub fn github_api_create_a_github_pages_site_with_source(
+    github_owner_or_organization: &str,
+    repo_name: &str,
+    pages_source: &PagesSource,
+) -> reqwest::blocking::RequestBuilder {
+    create_a_github_pages_site_request(github_owner_or_organization, repo_name, pages_source).request_builder()
+}
+
+/// GitHub API create-a-github-pages-site with the source branch and path, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_create_a_github_pages_site_with_source_async(
+    github_owner_or_organization: &str,
+    repo_name: &str,
+    pages_source: &PagesSource,
+) -> reqwest::RequestBuilder {
+    create_a_github_pages_site_request(github_owner_or_organization, repo_name, pages_source).async_request_builder()
+}
+
+/// Describe the request for github_api_create_a_github_pages_site_with_source()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn create_a_github_pages_site_request(github_owner_or_organization: &str, repo_name: &str, pages_source: &PagesSource) -> GitHubRequest {
     /*
         https://docs.github.com/en/rest/pages/pages?apiVersion=2022-11-28#create-a-github-pages-site
         curl -L \
@@ -501,97 +1582,14 @@ pub fn github_api_create_a_github_pages_site(github_owner_or_organization: &str,
         }
     }'
          */
-    let repos_url = format!("https://api.github.com/repos/{github_owner_or_organization}/{repo_name}/pages");
+    let api_base_url = github_endpoint().api_base_url;
+    let repos_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo_name}/pages");
     let body = serde_json::json!({
-        "build_type": "workflow",
+        "build_type": pages_source.build_type,
         "source": {
-            "branch": "main",
-            "path": "/docs"
+            "branch": pages_source.branch,
+            "path": pages_source.path
         }
     });
-    let body = body.to_string();
-
-    reqwest::blocking::Client::new()
-        .post(repos_url.as_str())
-        .header("Accept", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
-        .body(body)
-}
-
-/// Upload asset to github release  
-pub fn github_api_upload_asset_to_release(github_client: &impl SendToGitHubApi, github_owner_or_organization: &str, repo: &str, release_id: &str, path_to_file: &str) {
-    println!("    {YELLOW}Uploading file to GitHub release: {path_to_file}{RESET}");
-    let file = camino::Utf8Path::new(&path_to_file);
-    let file_name = file.file_name().unwrap();
-
-    let release_upload_url = format!("https://uploads.github.com/repos/{github_owner_or_organization}/{repo}/releases/{release_id}/assets");
-    let mut release_upload_url = <url::Url as std::str::FromStr>::from_str(&release_upload_url).unwrap();
-    release_upload_url.set_query(Some(format!("{}={}", "name", file_name).as_str()));
-    let file_size = std::fs::metadata(file).unwrap().len();
-    println!("    {YELLOW}It can take some time to upload. File size: {file_size}. Wait...{RESET}");
-    // region: async code made sync locally
-    let rt = tokio::runtime::Runtime::new().unwrap();
-    rt.block_on(async move {
-        let file = tokio::fs::File::open(file).await.unwrap();
-        let stream = tokio_util::codec::FramedRead::new(file, tokio_util::codec::BytesCodec::new());
-        let body = reqwest::Body::wrap_stream(stream);
-
-        let req = reqwest::Client::new()
-            .post(release_upload_url.as_str())
-            .header("Content-Type", "application/octet-stream")
-            .header("Content-Length", file_size.to_string())
-            .body(body);
-
-        github_client.upload_to_github(req).await;
-    });
-    // endregion: async code made sync locally
-}
-
-/// Create new release on Github
-pub fn github_api_create_new_release(github_owner_or_organization: &str, repo: &str, tag_name_version: &str, name: &str, branch: &str, body_md_text: &str) -> reqwest::blocking::RequestBuilder {
-    /*
-    https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#create-a-release
-    Request like :
-    curl -L \
-    -X POST \
-    -H "Accept: application/vnd.github+json" \
-    -H "Authorization: Bearer <YOUR-TOKEN>"\
-    -H "X-GitHub-Api-Version: 2022-11-28" \
-    https://api.github.com/repos/github_owner/REPO/releases \
-    -d '
-    {
-        "tag_name":"v1.0.0",
-        "target_commitish":"master",
-        "name":"v1.0.0",
-        "body":"Description of the release",
-        "draft":false,
-        "prerelease":false,
-        "generate_release_notes":false
-    }'
-
-    Response (short)
-    {
-    "id": 1,
-    ...
-    }
-    */
-    let releases_url = format!("https://api.github.com/repos/{github_owner_or_organization}/{repo}/releases");
-    let body = serde_json::json!({
-        "tag_name": tag_name_version,
-        "target_commitish":branch,
-        "name":name,
-        "body":body_md_text,
-        "draft":false,
-        "prerelease":false,
-        "generate_release_notes":false,
-    });
-    let body = body.to_string();
-
-    reqwest::blocking::Client::new()
-        .post(releases_url.as_str())
-        .header("Content-Type", "application/vnd.github+json")
-        .header("X-GitHub-Api-Version", "2022-11-28")
-        .header("User-Agent", "cargo_auto_lib")
-        .body(body)
+    GitHubRequest::from_url(reqwest::Method::POST, &repos_url, Some(body))
 }
@@ -0,0 +1,204 @@
+// dry_run_github_client_mod.rs
+
+//! GitHubClient for the dry run: it prints the planned requests and never sends anything.
+//!
+//! Before touching a production repository use the DryRunGitHubClient instead of the real GitHubClient:
+//! ```rust ignore
+//! let github_client = cgl::DryRunGitHubClient::new().user_login("bestia-dev");
+//! cgl::description_and_topics_to_github(&github_client);
+//! ```
+//! The mutating requests are printed with method, url and json body.
+//! The reads return plausible canned responses, so the task can continue and plan the next requests.
+//! There is no secret_token and no network. The library does not change local files and does not run git commands that change the repository.
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cargo_auto_lib as cl;
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cl::RESET;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cl::YELLOW;
+
+#[cfg(feature = "async")]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_request_mod::GitHubRequest;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_response_mod::GitHubResponse;
+use crate::mock_github_client_mod::RecordedRequest;
+
+/// GitHubClient that prints the planned requests and returns canned responses
+#[derive(Debug)]
+pub struct DryRunGitHubClient {
+    /// the login returned for the authenticated user
+    user_login: String,
+    planned_requests: std::sync::Mutex<Vec<RecordedRequest>>,
+}
+
+impl Default for DryRunGitHubClient {
+    fn default() -> Self {
+        DryRunGitHubClient {
+            user_login: "dry_run_user".to_string(),
+            planned_requests: std::sync::Mutex::new(vec![]),
+        }
+    }
+}
+
+impl DryRunGitHubClient {
+    /// New dry run client, the authenticated user is `dry_run_user`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The login of the authenticated user, usually the github_owner from Cargo.toml
+    pub fn user_login(mut self, user_login: &str) -> Self {
+        self.user_login = user_login.to_string();
+        self
+    }
+
+    /// All the requests planned until now
+    pub fn planned_requests(&self) -> Vec<RecordedRequest> {
+        self.planned_requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Record and print the request and return the canned response
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn plan(&self, recorded_request: RecordedRequest) -> GitHubResponse {
+        let github_request = GitHubRequest::from_url(
+            recorded_request.method.clone(),
+            &recorded_request.url,
+            recorded_request.body.clone(),
+        );
+        if github_request.method != reqwest::Method::GET {
+            println!(
+                "    {YELLOW}Dry run {}: {} {}{RESET}",
+                github_request.operation, github_request.method, github_request.url
+            );
+            if let Some(body) = &github_request.body {
+                println!("{}", serde_json::to_string_pretty(body).unwrap_or_default());
+            }
+        }
+        self.planned_requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(recorded_request);
+        self.canned_response(&github_request)
+    }
+
+    /// Plausible response for the request
+    ///
+    /// The reads return the authenticated user, the organization, the repository and empty lists.
+    /// Other reads return `Not Found`, like the release that does not exist yet.
+    /// The mutating requests return the request body with the fields required by the typed structs.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn canned_response(&self, github_request: &GitHubRequest) -> GitHubResponse {
+        let segments: Vec<&str> = github_request.path.trim_matches('/').split('/').collect();
+        let method = &github_request.method;
+        if method == reqwest::Method::DELETE {
+            return GitHubResponse::new(204, reqwest::header::HeaderMap::new(), serde_json::Value::Null);
+        }
+        if method == reqwest::Method::GET {
+            let body = match segments.as_slice() {
+                ["user"] => serde_json::json!({"login": self.user_login, "id": 0, "type": "User"}),
+                ["orgs", org] => serde_json::json!({"login": org, "id": 0}),
+                ["repos", owner, repo] => serde_json::json!({"id": 0, "name": repo, "full_name": format!("{owner}/{repo}"), "topics": []}),
+                ["rate_limit"] => {
+                    let rate = serde_json::json!({"limit": 5000, "remaining": 5000, "reset": 0, "used": 0, "resource": "core"});
+                    serde_json::json!({"resources": {"core": rate}, "rate": rate})
+                }
+                [.., "releases"] | [.., "assets"] => serde_json::json!([]),
+                _ => return GitHubResponse::from_json(serde_json::json!({"message": "Not Found", "status": "404"})),
+            };
+            return GitHubResponse::new(200, reqwest::header::HeaderMap::new(), body);
+        }
+
+        // the owner and the name of the repository or of the new object
+        let owner = match segments.as_slice() {
+            // the repository from the template has the owner in the body
+            ["repos", _, _, "generate"] => github_request
+                .body
+                .as_ref()
+                .and_then(|body| body.get("owner"))
+                .and_then(|owner| owner.as_str())
+                .unwrap_or(&self.user_login)
+                .to_string(),
+            ["repos", owner, ..] | ["orgs", owner, ..] => owner.to_string(),
+            _ => self.user_login.clone(),
+        };
+        let path_name = match segments.as_slice() {
+            ["repos", _, repo, ..] => repo.to_string(),
+            _ => String::new(),
+        };
+        let query_name = url::Url::parse(&github_request.url)
+            .ok()
+            .and_then(|url| url.query_pairs().find(|(key, _)| key == "name").map(|(_, value)| value.to_string()));
+        let mut body = match &github_request.body {
+            Some(serde_json::Value::Object(object)) => object.clone(),
+            _ => serde_json::Map::new(),
+        };
+        let name = body
+            .get("name")
+            .and_then(|name| name.as_str())
+            .map(|name| name.to_string())
+            .or(query_name)
+            .unwrap_or(path_name);
+        if body.get("owner").is_some_and(|owner| owner.is_string()) {
+            body.insert("owner".to_string(), serde_json::json!({"login": owner, "id": 0}));
+        }
+        body.entry("id").or_insert(serde_json::json!(0));
+        body.entry("full_name").or_insert(serde_json::json!(format!("{owner}/{name}")));
+        body.entry("name").or_insert(serde_json::json!(name));
+        body.entry("tag_name").or_insert(serde_json::json!(""));
+        let status = if method == reqwest::Method::POST { 201 } else { 200 };
+        GitHubResponse::new(status, reqwest::header::HeaderMap::new(), serde_json::Value::Object(body))
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl SendToGitHubApi for DryRunGitHubClient {
+    /// Print the request and return the canned response
+    fn send_to_github_api(&self, req: reqwest::blocking::RequestBuilder) -> serde_json::Value {
+        self.send_to_github_api_with_response(req).body
+    }
+
+    /// Print the request and return the canned response
+    fn send_to_github_api_with_response(&self, req: reqwest::blocking::RequestBuilder) -> GitHubResponse {
+        match req.build() {
+            Ok(request) => self.plan(RecordedRequest::from_blocking_request(&request)),
+            Err(e) => GitHubResponse::from_json(serde_json::json!({ "message": e.to_string() })),
+        }
+    }
+
+    /// Print the upload and return the canned asset
+    ///
+    /// The file stream is never read.
+    fn upload_to_github(&self, req: reqwest::RequestBuilder) -> impl std::future::Future<Output = serde_json::Value> + Send {
+        let response = match req.build() {
+            Ok(request) => self.plan(RecordedRequest::from_request(&request)).body,
+            Err(e) => serde_json::json!({ "message": e.to_string() }),
+        };
+        async move { response }
+    }
+
+    fn is_dry_run(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "async")]
+impl SendToGitHubApiAsync for DryRunGitHubClient {
+    /// Print the request and return the canned response
+    fn send_to_github_api_async(&self, req: reqwest::RequestBuilder) -> impl std::future::Future<Output = GitHubResponse> + Send {
+        let response = match req.build() {
+            Ok(request) => self.plan(RecordedRequest::from_request(&request)),
+            Err(e) => GitHubResponse::from_json(serde_json::json!({ "message": e.to_string() })),
+        };
+        async move { response }
+    }
+
+    fn is_dry_run(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,102 @@
+// error_mod.rs
+
+//! Error library for this crate using thiserror.
+//!
+//! I am using the crate thiserror to create an enum for all library errors.
+//! The `try_` functions return this errors, so the automation task can retry, fall back or print a friendly summary.
+//! The old functions without the `try_` prefix just panic with the error message, like they always did.
+
+/// Enum of possible errors from this library
+#[derive(thiserror::Error, Debug)]
+pub enum GitHubLibError {
+    #[error("HttpStatusError: GitHub API {operation} returned status {status}.")]
+    HttpStatusError { operation: String, status: u16 },
+
+    #[error("GitHubApiError: GitHub API {operation} returned: {message} {}", errors.join(" "))]
+    GitHubApiError {
+        operation: String,
+        /// the HTTP status code if GitHub returned it
+        status: Option<u16>,
+        /// the `message` field from the GitHub response
+        message: String,
+        /// the messages or codes from the `errors` array in the GitHub response
+        errors: Vec<String>,
+    },
+
+    #[error("UnexpectedResponseError: GitHub API {operation} response does not contain the field {field}.")]
+    UnexpectedResponseError { operation: String, field: String },
+
+    #[error("RequestNotAllowedError: The GitHubClient does not allow the operation {operation}: {method} {path}")]
+    RequestNotAllowedError { operation: String, method: String, path: String },
+
+    #[error("GitCommandError: {command}: {message}")]
+    GitCommandError { command: String, message: String },
+
+    #[error("CargoTomlError: {0}")]
+    CargoTomlError(String),
+
+    #[error("StdIoError: {0}")]
+    StdIoError(#[from] std::io::Error),
+
+    #[error("SerdeJsonError: {0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+
+    #[error("FromUtf8Error: {0}")]
+    FromUtf8Error(#[from] std::string::FromUtf8Error),
+
+    #[error("UrlParseError: {0}")]
+    UrlParseError(#[from] url::ParseError),
+
+    #[error("{0}")]
+    ErrorFromString(String),
+
+    #[error("{0}")]
+    ErrorFromStr(&'static str),
+}
+
+impl GitHubLibError {
+    /// The GitHub API returned `404 Not Found`
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            GitHubLibError::GitHubApiError { status, message, .. } => *status == Some(404) || message == "Not Found",
+            GitHubLibError::HttpStatusError { status, .. } => *status == 404,
+            _ => false,
+        }
+    }
+}
+
+/// Result type alias with fixed GitHubLibError using thiserror
+///
+/// It makes simpler to write returns from functions.
+pub type ResultWithGitHubLibError<T, E = GitHubLibError> = core::result::Result<T, E>;
+
+/// Check the json response from GitHub API for the error fields `message` and `errors`
+///
+/// The successful responses from GitHub do not contain the field `message`.
+/// It returns the same json_value if there is no error.
+pub fn check_github_api_response(operation: &str, json_value: serde_json::Value) -> ResultWithGitHubLibError<serde_json::Value> {
+    let Some(message) = json_value.get("message").and_then(|m| m.as_str()) else {
+        return Ok(json_value);
+    };
+    // newer GitHub responses contain also the status as a string
+    let status = json_value
+        .get("status")
+        .and_then(|s| s.as_str())
+        .and_then(|s| s.parse::<u16>().ok());
+    let mut errors = vec![];
+    if let Some(errors_array) = json_value.get("errors").and_then(|e| e.as_array()) {
+        for error in errors_array.iter() {
+            if let Some(error_message) = error.get("message").and_then(|m| m.as_str()) {
+                errors.push(error_message.to_string());
+            } else if let Some(code) = error.get("code").and_then(|c| c.as_str()) {
+                errors.push(code.to_string());
+            }
+        }
+    }
+    Err(GitHubLibError::GitHubApiError {
+        operation: operation.to_string(),
+        status,
+        message: message.to_string(),
+        errors,
+    })
+}
@@ -0,0 +1,81 @@
+// github_endpoint_mod.rs
+
+//! The base URLs of the GitHub API and GitHub uploads.
+//!
+//! By default all the requests go to `https://api.github.com` and `https://uploads.github.com`.
+//! For GitHub Enterprise Server or a local test server, call set_github_endpoint() once
+//! at the start of the automation task. All the request builders in this library use it.
+
+/// Base URLs for the GitHub API and uploads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubEndpoint {
+    /// base URL for the API without the trailing slash, like `https://api.github.com`
+    pub api_base_url: String,
+    /// base URL for uploading release assets without the trailing slash, like `https://uploads.github.com`
+    pub uploads_base_url: String,
+    /// host for git over SSH, like `github.com`
+    pub git_ssh_host: String,
+}
+
+impl Default for GitHubEndpoint {
+    fn default() -> Self {
+        GitHubEndpoint {
+            api_base_url: "https://api.github.com".to_string(),
+            uploads_base_url: "https://uploads.github.com".to_string(),
+            git_ssh_host: "github.com".to_string(),
+        }
+    }
+}
+
+impl GitHubEndpoint {
+    /// Endpoint with custom base URLs, like a local test server `http://127.0.0.1:8080`
+    pub fn new(api_base_url: &str, uploads_base_url: &str, git_ssh_host: &str) -> Self {
+        GitHubEndpoint {
+            api_base_url: api_base_url.trim_end_matches('/').to_string(),
+            uploads_base_url: uploads_base_url.trim_end_matches('/').to_string(),
+            git_ssh_host: git_ssh_host.to_string(),
+        }
+    }
+
+    /// Endpoint for GitHub Enterprise Server with the hostname like `ghe.corp`
+    ///
+    /// The API is on `https://ghe.corp/api/v3` and uploads on `https://ghe.corp/api/uploads`.
+    pub fn github_enterprise_server(hostname: &str) -> Self {
+        GitHubEndpoint {
+            api_base_url: format!("https://{hostname}/api/v3"),
+            uploads_base_url: format!("https://{hostname}/api/uploads"),
+            git_ssh_host: hostname.to_string(),
+        }
+    }
+
+    /// Check if the url of a request goes to this API endpoint
+    ///
+    /// The GitHubClient in the calling crate can use it to check the url before adding the secret_token.
+    pub fn is_api_url(&self, url: &url::Url) -> bool {
+        url.as_str().starts_with(&format!("{}/", self.api_base_url))
+    }
+
+    /// Check if the url of a request goes to this uploads endpoint
+    ///
+    /// The GitHubClient in the calling crate can use it to check the url before adding the secret_token.
+    pub fn is_uploads_url(&self, url: &url::Url) -> bool {
+        url.as_str().starts_with(&format!("{}/", self.uploads_base_url))
+    }
+}
+
+/// The endpoint is stored for the whole library. None means the default github.com.
+static GITHUB_ENDPOINT: std::sync::RwLock<Option<GitHubEndpoint>> = std::sync::RwLock::new(None);
+
+/// Set the endpoint for all the requests from this library
+pub fn set_github_endpoint(github_endpoint: GitHubEndpoint) {
+    *GITHUB_ENDPOINT.write().unwrap_or_else(|e| e.into_inner()) = Some(github_endpoint);
+}
+
+/// Get the endpoint used for all the requests from this library
+pub fn github_endpoint() -> GitHubEndpoint {
+    GITHUB_ENDPOINT
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
@@ -0,0 +1,68 @@
+// github_headers_mod.rs
+
+//! Headers added to every request to GitHub API.
+//!
+//! The User-Agent identifies the tool in the audit logs of the organization.
+//! The X-GitHub-Api-Version selects the version of the REST API.
+//! The headers are stored for the whole library. Set them once at the start of the automation task:
+//! ```rust ignore
+//! cgl::set_github_headers(cgl::GitHubHeaders::new().user_agent("my_org_release_tool/1.0").extra_header("X-Team", "platform"));
+//! ```
+
+/// Headers for all the requests from this library
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubHeaders {
+    pub user_agent: String,
+    /// the version of the REST API like `2022-11-28`
+    pub api_version: String,
+    /// additional headers as name and value
+    pub extra_headers: Vec<(String, String)>,
+}
+
+impl Default for GitHubHeaders {
+    fn default() -> Self {
+        GitHubHeaders {
+            user_agent: "cargo_auto_lib".to_string(),
+            api_version: "2022-11-28".to_string(),
+            extra_headers: vec![],
+        }
+    }
+}
+
+impl GitHubHeaders {
+    /// The default headers: User-Agent `cargo_auto_lib` and API version `2022-11-28`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The User-Agent header
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// The X-GitHub-Api-Version header
+    pub fn api_version(mut self, api_version: &str) -> Self {
+        self.api_version = api_version.to_string();
+        self
+    }
+
+    /// Add a header to every request
+    pub fn extra_header(mut self, name: &str, value: &str) -> Self {
+        self.extra_headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// The headers are stored for the whole library. None means the default headers.
+static GITHUB_HEADERS: std::sync::RwLock<Option<GitHubHeaders>> = std::sync::RwLock::new(None);
+
+/// Set the headers for all the requests from this library
+pub fn set_github_headers(github_headers: GitHubHeaders) {
+    *GITHUB_HEADERS.write().unwrap_or_else(|e| e.into_inner()) = Some(github_headers);
+}
+
+/// Get the headers used for all the requests from this library
+pub fn github_headers() -> GitHubHeaders {
+    GITHUB_HEADERS.read().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default()
+}
@@ -0,0 +1,277 @@
+// github_metadata_mod.rs
+
+//! Repository settings from the `[package.metadata.github]` table in Cargo.toml.
+//!
+//! The settings live next to the crate manifest and not in the source code of the automation task:
+//! ```toml
+//! [package.metadata.github]
+//! visibility = "public"
+//! has_issues = true
+//! has_discussions = true
+//! default_branch = "main"
+//! homepage = "https://bestia.dev"
+//! pages = { build_type = "workflow", branch = "main", path = "/docs" }
+//! allow_squash_merge = true
+//! delete_branch_on_merge = true
+//! topics = ["automation", "github"]
+//! ```
+//! The new repository is created with these settings and description_and_topics_to_github() syncs them to the existing repository.
+//! The settings that are not in the table keep the GitHub value.
+//! Without the homepage in the table, the homepage or documentation of the package is the homepage for the new and the existing repository.
+//! The sync takes also the categories from the package in Cargo.toml.
+
+use crate::auto_github_api_mod::RepositoryVisibility;
+use crate::auto_github_api_mod::UpdateRepository;
+use crate::error_mod::GitHubLibError;
+use crate::error_mod::ResultWithGitHubLibError;
+use crate::github_types_mod::Repository;
+
+/// The `[package.metadata.github]` table in Cargo.toml
+///
+/// Unknown keys are an error, so a typo does not silently keep the GitHub value.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GitHubMetadata {
+    pub visibility: Option<RepositoryVisibility>,
+    pub has_issues: Option<bool>,
+    pub has_projects: Option<bool>,
+    pub has_wiki: Option<bool>,
+    pub has_discussions: Option<bool>,
+    /// the branch of the first push of the new repository and the default branch of the existing repository
+    pub default_branch: Option<String>,
+    pub homepage: Option<String>,
+    /// the source of the GitHub pages for the new repository
+    pub pages: Option<PagesSource>,
+    pub allow_squash_merge: Option<bool>,
+    pub allow_merge_commit: Option<bool>,
+    pub allow_rebase_merge: Option<bool>,
+    pub delete_branch_on_merge: Option<bool>,
+    /// topics added to the keywords from Cargo.toml
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+/// Source of the GitHub pages
+///
+/// The default is the GitHub action workflow, like it was always.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PagesSource {
+    /// `workflow` for the GitHub action or `legacy` for the branch and path
+    pub build_type: String,
+    pub branch: String,
+    pub path: String,
+}
+
+impl Default for PagesSource {
+    fn default() -> Self {
+        PagesSource {
+            build_type: "workflow".to_string(),
+            branch: "main".to_string(),
+            path: "/docs".to_string(),
+        }
+    }
+}
+
+/// One field changed on GitHub by the metadata sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepositoryMetadataChange {
+    /// the name of the field in GitHub API like `homepage` or `has_wiki`
+    pub field: String,
+    pub old_value: serde_json::Value,
+    pub new_value: serde_json::Value,
+}
+
+impl std::fmt::Display for RepositoryMetadataChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.old_value, self.new_value)
+    }
+}
+
+/// The package fields in Cargo.toml for the new repository and the metadata sync
+///
+/// Unlike CargoToml::read() the reader returns an error instead of panic.
+pub(crate) struct CargoTomlPackageMetadata {
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) name: String,
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) description: Option<String>,
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) repository: Option<String>,
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) keywords: Vec<String>,
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) categories: Vec<String>,
+    pub(crate) github_metadata: GitHubMetadata,
+}
+
+/// The `[package.metadata]` table with only the `github` table inside
+#[derive(serde::Deserialize, Debug, Default)]
+struct PackageMetadata {
+    github: Option<GitHubMetadata>,
+}
+
+impl GitHubMetadata {
+    /// The topics for GitHub: the keywords from Cargo.toml and then the extra topics without duplicates
+    pub fn topics_with_keywords(&self, keywords: &[String]) -> Vec<String> {
+        let mut topics: Vec<String> = vec![];
+        for topic in keywords.iter().chain(self.topics.iter()) {
+            if !topics.contains(topic) {
+                topics.push(topic.clone());
+            }
+        }
+        topics
+    }
+
+    /// The settings that are different on GitHub
+    ///
+    /// The settings that are not in the table are not compared.
+    pub fn repository_changes(&self, repository: &Repository) -> UpdateRepository {
+        let mut update_repository = UpdateRepository::new();
+        if let Some(visibility) = self.visibility {
+            let github_visibility = match repository.visibility.as_deref() {
+                Some(github_visibility) => github_visibility,
+                None if repository.private => RepositoryVisibility::Private.as_str(),
+                None => RepositoryVisibility::Public.as_str(),
+            };
+            if visibility.as_str() != github_visibility {
+                update_repository = update_repository.visibility(visibility);
+            }
+        }
+        if let Some(homepage) = &self.homepage {
+            if repository.homepage.as_deref().unwrap_or_default() != homepage {
+                update_repository = update_repository.homepage(homepage);
+            }
+        }
+        if let Some(default_branch) = &self.default_branch {
+            if repository.default_branch.as_ref() != Some(default_branch) {
+                update_repository = update_repository.default_branch(default_branch);
+            }
+        }
+        if let Some(has_issues) = self.has_issues.filter(|has_issues| *has_issues != repository.has_issues) {
+            update_repository = update_repository.has_issues(has_issues);
+        }
+        if let Some(has_projects) = self.has_projects.filter(|has_projects| *has_projects != repository.has_projects) {
+            update_repository = update_repository.has_projects(has_projects);
+        }
+        if let Some(has_wiki) = self.has_wiki.filter(|has_wiki| *has_wiki != repository.has_wiki) {
+            update_repository = update_repository.has_wiki(has_wiki);
+        }
+        if let Some(has_discussions) = self
+            .has_discussions
+            .filter(|has_discussions| *has_discussions != repository.has_discussions)
+        {
+            update_repository = update_repository.has_discussions(has_discussions);
+        }
+        // the merge settings are returned only to the users with admin rights, so None is also different
+        if let Some(allow_squash_merge) = self.allow_squash_merge.filter(|x| Some(*x) != repository.allow_squash_merge) {
+            update_repository = update_repository.allow_squash_merge(allow_squash_merge);
+        }
+        if let Some(allow_merge_commit) = self.allow_merge_commit.filter(|x| Some(*x) != repository.allow_merge_commit) {
+            update_repository = update_repository.allow_merge_commit(allow_merge_commit);
+        }
+        if let Some(allow_rebase_merge) = self.allow_rebase_merge.filter(|x| Some(*x) != repository.allow_rebase_merge) {
+            update_repository = update_repository.allow_rebase_merge(allow_rebase_merge);
+        }
+        if let Some(delete_branch_on_merge) = self
+            .delete_branch_on_merge
+            .filter(|x| Some(*x) != repository.delete_branch_on_merge)
+        {
+            update_repository = update_repository.delete_branch_on_merge(delete_branch_on_merge);
+        }
+        update_repository
+    }
+}
+
+/// Read the `[package.metadata.github]` table from Cargo.toml in the current directory
+///
+/// For workspaces it is the Cargo.toml of the first member, like CargoToml::read().
+/// Without the table all the settings are None, only the homepage falls back to the homepage or documentation of the package.
+pub fn cargo_toml_github_metadata() -> ResultWithGitHubLibError<GitHubMetadata> {
+    Ok(cargo_toml_package_metadata()?.github_metadata)
+}
+
+/// Read the package fields for the metadata sync from Cargo.toml in the current directory
+pub(crate) fn cargo_toml_package_metadata() -> ResultWithGitHubLibError<CargoTomlPackageMetadata> {
+    let cargo_toml_path = std::path::Path::new("Cargo.toml");
+    if !cargo_toml_path.exists() {
+        return Err(GitHubLibError::CargoTomlError(
+            "The file Cargo.toml does not exist in the current directory.".to_string(),
+        ));
+    }
+    let mut manifest = read_manifest(cargo_toml_path)?;
+    if let Some(main_member) = manifest.workspace.as_ref().and_then(|workspace| workspace.members.first()) {
+        manifest = read_manifest(&std::path::Path::new(main_member).join("Cargo.toml"))?;
+    }
+    let Some(package) = manifest.package else {
+        return Err(GitHubLibError::CargoTomlError(
+            "Element Package in Cargo.toml does not exist!".to_string(),
+        ));
+    };
+    let mut github_metadata = package
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.github.clone())
+        .unwrap_or_default();
+    // the homepage from the table wins over the homepage and the documentation of the package
+    if github_metadata.homepage.is_none() {
+        github_metadata.homepage = [&package.homepage, &package.documentation]
+            .into_iter()
+            .find_map(|url| url.as_ref().and_then(|url| url.get().ok()))
+            .cloned();
+    }
+    Ok(CargoTomlPackageMetadata {
+        #[cfg(any(feature = "blocking", feature = "async"))]
+        name: package.name.clone(),
+        #[cfg(any(feature = "blocking", feature = "async"))]
+        description: package.description.as_ref().and_then(|description| description.get().ok()).cloned(),
+        #[cfg(any(feature = "blocking", feature = "async"))]
+        repository: package.repository.as_ref().and_then(|repository| repository.get().ok()).cloned(),
+        #[cfg(any(feature = "blocking", feature = "async"))]
+        keywords: package.keywords.get().cloned().unwrap_or_default(),
+        #[cfg(any(feature = "blocking", feature = "async"))]
+        categories: package.categories.get().cloned().unwrap_or_default(),
+        github_metadata,
+    })
+}
+
+/// The category from Cargo.toml as GitHub topic
+///
+/// The topic has only lowercase letters, numbers and hyphens, so `development-tools::build-utils` becomes `development-tools-build-utils`.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) fn category_to_topic(category: &str) -> String {
+    category.replace("::", "-")
+}
+
+/// The changes for the report: the new values from UpdateRepository and the old values from GitHub
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) fn repository_metadata_changes(update_repository: &UpdateRepository, repository: &Repository) -> Vec<RepositoryMetadataChange> {
+    let serde_json::Value::Object(new_values) = update_repository.to_json() else {
+        return vec![];
+    };
+    let old_values = serde_json::to_value(repository).unwrap_or_default();
+    new_values
+        .into_iter()
+        .map(|(field, new_value)| {
+            let old_value = match (field.as_str(), &old_values[field.as_str()]) {
+                // the visibility is missing in the older GitHub Enterprise Server
+                ("visibility", serde_json::Value::Null) if repository.private => serde_json::json!("private"),
+                ("visibility", serde_json::Value::Null) => serde_json::json!("public"),
+                (_, old_value) => old_value.clone(),
+            };
+            RepositoryMetadataChange {
+                field,
+                old_value,
+                new_value,
+            }
+        })
+        .collect()
+}
+
+/// Read the manifest with the package metadata
+///
+/// The absolute path is needed to find the parent workspace.
+fn read_manifest(cargo_toml_path: &std::path::Path) -> ResultWithGitHubLibError<cargo_toml::Manifest<PackageMetadata>> {
+    cargo_toml::Manifest::<PackageMetadata>::from_path_with_metadata(std::path::absolute(cargo_toml_path)?)
+        .map_err(|e| GitHubLibError::CargoTomlError(format!("{}: {e}", cargo_toml_path.display())))
+}
@@ -0,0 +1,635 @@
+// github_release_asset_mod.rs
+
+//! Release assets: upload, list, get, rename, delete and replace assets.
+//!
+//! The github_api_* functions only create the RequestBuilder.
+//! The other functions send the request with the GitHubClient and return the typed result.
+
+#[cfg(feature = "upload")]
+use cargo_auto_lib as cl;
+
+#[cfg(all(feature = "blocking", feature = "upload"))]
+use cl::RED;
+#[cfg(feature = "upload")]
+use cl::RESET;
+#[cfg(feature = "upload")]
+use cl::YELLOW;
+
+#[cfg(feature = "blocking")]
+use crate::async_github_client_mod::BlockingGitHubClient;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+#[cfg(feature = "upload")]
+use crate::error_mod::GitHubLibError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::error_mod::ResultWithGitHubLibError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_endpoint_mod::github_endpoint;
+#[cfg(feature = "upload")]
+use crate::github_headers_mod::github_headers;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_request_mod::GitHubRequest;
+#[cfg(feature = "upload")]
+use crate::github_types_mod::github_response_to;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::send_to_github_api_as_async;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::ReleaseAsset;
+#[cfg(feature = "upload")]
+use crate::http_client_mod::async_http_client;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::pagination_mod::paginate_async;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::pagination_mod::MAX_PER_PAGE;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::retry_mod::send_to_github_api_with_retry_async;
+#[cfg(feature = "upload")]
+use crate::retry_mod::RetryState;
+#[cfg(all(feature = "blocking", feature = "upload"))]
+use crate::upload_progress_mod::default_upload_progress;
+#[cfg(feature = "upload")]
+use crate::upload_progress_mod::UploadProgress;
+#[cfg(feature = "blocking")]
+use crate::utils_mod::block_on;
+#[cfg(all(feature = "blocking", feature = "upload"))]
+use crate::utils_mod::block_on_in_tokio_runtime;
+#[cfg(feature = "upload")]
+use crate::utils_mod::sleep_async;
+
+/// Upload asset to github release  
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub fn github_api_upload_asset_to_release(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: &str,
+    path_to_file: &str,
+) {
+    try_github_api_upload_asset_to_release(github_client, github_owner_or_organization, repo, release_id, path_to_file)
+        .unwrap_or_else(|e| panic!("{RED}{e}{RESET}"));
+}
+
+/// Upload asset to github release, returns error instead of panic
+///
+/// Returns the data of the new asset from GitHub.
+/// In the terminal it shows the progress bar.
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub fn try_github_api_upload_asset_to_release(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: &str,
+    path_to_file: &str,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    try_github_api_upload_asset_to_release_with_progress(
+        github_client,
+        github_owner_or_organization,
+        repo,
+        release_id,
+        path_to_file,
+        default_upload_progress(),
+    )
+}
+
+/// Upload asset to github release and report the progress to the callback
+///
+/// Returns the data of the new asset from GitHub.
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub fn try_github_api_upload_asset_to_release_with_progress(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: &str,
+    path_to_file: &str,
+    upload_progress: Option<std::sync::Arc<dyn UploadProgress>>,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    block_on_in_tokio_runtime(upload_asset_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        release_id,
+        path_to_file,
+        upload_progress,
+    ))?
+}
+
+/// Upload the file as a stream to the release and report the progress to the callback, async
+///
+/// Returns the data of the new asset from GitHub.
+/// The async code must run inside a tokio runtime because of tokio::fs.
+#[cfg(feature = "upload")]
+pub async fn upload_asset_to_release_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    path_to_file: &str,
+    upload_progress: Option<std::sync::Arc<dyn UploadProgress>>,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    upload_asset_async(
+        github_client,
+        github_owner_or_organization,
+        repo,
+        &release_id.to_string(),
+        path_to_file,
+        upload_progress,
+    )
+    .await
+}
+
+/// Upload the file as a stream to the release
+///
+/// The legacy functions have the release_id as string.
+#[cfg(feature = "upload")]
+async fn upload_asset_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: &str,
+    path_to_file: &str,
+    upload_progress: Option<std::sync::Arc<dyn UploadProgress>>,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    use futures_util::StreamExt;
+
+    println!("    {YELLOW}Uploading file to GitHub release: {path_to_file}{RESET}");
+    let file = camino::Utf8Path::new(&path_to_file);
+    let file_name = file
+        .file_name()
+        .ok_or_else(|| GitHubLibError::ErrorFromString(format!("The path {path_to_file} does not contain a file name.")))?;
+
+    let uploads_base_url = github_endpoint().uploads_base_url;
+    let release_upload_url = format!("{uploads_base_url}/repos/{github_owner_or_organization}/{repo}/releases/{release_id}/assets");
+    let mut release_upload_url = <url::Url as std::str::FromStr>::from_str(&release_upload_url)?;
+    release_upload_url.query_pairs_mut().append_pair("name", file_name);
+    github_client.authorize_github_request(&GitHubRequest::new(reqwest::Method::POST, &release_upload_url, None))?;
+    let file_size = tokio::fs::metadata(file).await?.len();
+    println!("    {YELLOW}It can take some time to upload. File size: {file_size}. Wait...{RESET}");
+
+    // the stream cannot be cloned, so the file is opened again for every attempt
+    let mut retry_state = RetryState::new();
+    loop {
+        let file = tokio::fs::File::open(file).await?;
+        let stream = tokio_util::codec::FramedRead::new(file, tokio_util::codec::BytesCodec::new());
+        // count the bytes of every chunk for the progress callback
+        let file_name_for_progress = file_name.to_string();
+        let upload_progress = upload_progress.clone();
+        let mut bytes_sent = 0;
+        let stream = stream.map(move |chunk| {
+            if let (Ok(bytes), Some(upload_progress)) = (&chunk, &upload_progress) {
+                bytes_sent += bytes.len() as u64;
+                upload_progress.on_progress(&file_name_for_progress, bytes_sent, file_size);
+            }
+            chunk
+        });
+        let body = reqwest::Body::wrap_stream(stream);
+
+        let github_headers = github_headers();
+        let mut req = async_http_client()
+            .post(release_upload_url.as_str())
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", &github_headers.api_version)
+            .header("User-Agent", &github_headers.user_agent)
+            .header("Content-Type", "application/octet-stream")
+            .header("Content-Length", file_size.to_string());
+        for (name, value) in &github_headers.extra_headers {
+            req = req.header(name, value);
+        }
+        let req = req.body(body);
+
+        let github_response = github_client.send_to_github_api_async(req).await;
+        let Some(wait) = retry_state.wait_before_retry_async(github_client, &github_response).await else {
+            return github_response_to("upload_asset_to_release", github_response);
+        };
+        println!("    {YELLOW}Upload failed. Retry in {} seconds.{RESET}", wait.as_secs());
+        sleep_async(wait).await;
+        // the failed upload can leave the asset in the state starter, then the retry fails with already_exists
+        let release_id = release_id
+            .parse::<i64>()
+            .map_err(|e| GitHubLibError::ErrorFromString(format!("The release_id {release_id} is not a number: {e}")))?;
+        for asset in list_release_assets_async(github_client, github_owner_or_organization, repo, release_id).await? {
+            if asset.name == file_name && asset.state.as_deref() == Some("starter") {
+                delete_release_asset_async(github_client, github_owner_or_organization, repo, asset.id).await?;
+            }
+        }
+    }
+}
+
+/// Result of the upload for one file
+#[derive(Debug)]
+#[cfg(feature = "upload")]
+pub struct AssetUploadResult {
+    pub path_to_file: String,
+    pub result: ResultWithGitHubLibError<ReleaseAsset>,
+}
+
+/// Upload many files to the release concurrently
+///
+/// All the uploads run on one tokio runtime. At most max_concurrency uploads run at the same time.
+/// One failed upload does not stop the others.
+/// The report has the same order as paths_to_files.
+/// The upload_progress is called with the file_name, so one callback can show all the files.
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub fn upload_assets_to_release(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    paths_to_files: &[&str],
+    max_concurrency: usize,
+    upload_progress: Option<std::sync::Arc<dyn UploadProgress>>,
+) -> ResultWithGitHubLibError<Vec<AssetUploadResult>> {
+    Ok(block_on_in_tokio_runtime(upload_assets_to_release_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        release_id,
+        paths_to_files,
+        max_concurrency,
+        upload_progress,
+    ))?)
+}
+
+/// Upload many files to the release concurrently, async
+///
+/// Look at the upload_assets_to_release() for details.
+#[cfg(feature = "upload")]
+pub async fn upload_assets_to_release_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    paths_to_files: &[&str],
+    max_concurrency: usize,
+    upload_progress: Option<std::sync::Arc<dyn UploadProgress>>,
+) -> Vec<AssetUploadResult> {
+    use futures_util::StreamExt;
+
+    futures_util::stream::iter(paths_to_files.iter())
+        .map(|path_to_file| {
+            let upload_progress = upload_progress.clone();
+            async move {
+                let result = upload_asset_to_release_async(
+                    github_client,
+                    github_owner_or_organization,
+                    repo,
+                    release_id,
+                    path_to_file,
+                    upload_progress,
+                )
+                .await;
+                AssetUploadResult {
+                    path_to_file: path_to_file.to_string(),
+                    result,
+                }
+            }
+        })
+        .buffered(max_concurrency.max(1))
+        .collect::<Vec<AssetUploadResult>>()
+        .await
+}
+
+/// GitHub API list release assets, one page
+#[cfg(feature = "blocking")]
+pub fn github_api_list_release_assets(
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    per_page: u32,
+    page: u32,
+) -> reqwest::blocking::RequestBuilder {
+    list_release_assets_request(github_owner_or_organization, repo, release_id, per_page, page).request_builder()
+}
+
+/// GitHub API list release assets, one page, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_list_release_assets_async(
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    per_page: u32,
+    page: u32,
+) -> reqwest::RequestBuilder {
+    list_release_assets_request(github_owner_or_organization, repo, release_id, per_page, page).async_request_builder()
+}
+
+/// Describe the request for github_api_list_release_assets()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn list_release_assets_request(github_owner_or_organization: &str, repo: &str, release_id: i64, per_page: u32, page: u32) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#list-release-assets
+
+    curl -L \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    "https://api.github.com/repos/OWNER/REPO/releases/RELEASE_ID/assets?per_page=30&page=1"
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let assets_url =
+        format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/{release_id}/assets?per_page={per_page}&page={page}");
+    GitHubRequest::from_url(reqwest::Method::GET, &assets_url, None)
+}
+
+/// GitHub API get a release asset
+#[cfg(feature = "blocking")]
+pub fn github_api_get_release_asset(github_owner_or_organization: &str, repo: &str, asset_id: i64) -> reqwest::blocking::RequestBuilder {
+    get_release_asset_request(github_owner_or_organization, repo, asset_id).request_builder()
+}
+
+/// GitHub API get a release asset, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_get_release_asset_async(github_owner_or_organization: &str, repo: &str, asset_id: i64) -> reqwest::RequestBuilder {
+    get_release_asset_request(github_owner_or_organization, repo, asset_id).async_request_builder()
+}
+
+/// Describe the request for github_api_get_release_asset()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn get_release_asset_request(github_owner_or_organization: &str, repo: &str, asset_id: i64) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#get-a-release-asset
+
+    curl -L \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/OWNER/REPO/releases/assets/ASSET_ID
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let asset_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/assets/{asset_id}");
+    GitHubRequest::from_url(reqwest::Method::GET, &asset_url, None)
+}
+
+/// GitHub API update a release asset: rename and change the label
+#[cfg(feature = "blocking")]
+pub fn github_api_update_release_asset(
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+    name: &str,
+    label: Option<&str>,
+) -> reqwest::blocking::RequestBuilder {
+    update_release_asset_request(github_owner_or_organization, repo, asset_id, name, label).request_builder()
+}
+
+/// GitHub API update a release asset: rename and change the label, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_update_release_asset_async(
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+    name: &str,
+    label: Option<&str>,
+) -> reqwest::RequestBuilder {
+    update_release_asset_request(github_owner_or_organization, repo, asset_id, name, label).async_request_builder()
+}
+
+/// Describe the request for github_api_update_release_asset()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn update_release_asset_request(
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+    name: &str,
+    label: Option<&str>,
+) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#update-a-release-asset
+
+    curl -L \
+    -X PATCH \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/OWNER/REPO/releases/assets/ASSET_ID \
+    -d '{"name":"foo-1.0.0-osx.zip","label":"Mac binary"}'
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let asset_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/assets/{asset_id}");
+    let mut body = serde_json::json!({
+        "name": name,
+    });
+    if let Some(label) = label {
+        body["label"] = serde_json::json!(label);
+    }
+    GitHubRequest::from_url(reqwest::Method::PATCH, &asset_url, Some(body))
+}
+
+/// GitHub API delete a release asset
+///
+/// The response is `204 No Content` without a json body.
+#[cfg(feature = "blocking")]
+pub fn github_api_delete_release_asset(github_owner_or_organization: &str, repo: &str, asset_id: i64) -> reqwest::blocking::RequestBuilder {
+    delete_release_asset_request(github_owner_or_organization, repo, asset_id).request_builder()
+}
+
+/// GitHub API delete a release asset, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_delete_release_asset_async(github_owner_or_organization: &str, repo: &str, asset_id: i64) -> reqwest::RequestBuilder {
+    delete_release_asset_request(github_owner_or_organization, repo, asset_id).async_request_builder()
+}
+
+/// Describe the request for github_api_delete_release_asset()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn delete_release_asset_request(github_owner_or_organization: &str, repo: &str, asset_id: i64) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#delete-a-release-asset
+
+    curl -L \
+    -X DELETE \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/OWNER/REPO/releases/assets/ASSET_ID
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let asset_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/assets/{asset_id}");
+    GitHubRequest::from_url(reqwest::Method::DELETE, &asset_url, None)
+}
+
+/// List all the assets of the release, page after page
+#[cfg(feature = "blocking")]
+pub fn list_release_assets(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+) -> ResultWithGitHubLibError<Vec<ReleaseAsset>> {
+    block_on(list_release_assets_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        release_id,
+    ))
+}
+
+/// List all the assets of the release, page after page, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn list_release_assets_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+) -> ResultWithGitHubLibError<Vec<ReleaseAsset>> {
+    paginate_async(github_client, MAX_PER_PAGE, |per_page, page| {
+        github_api_list_release_assets_async(github_owner_or_organization, repo, release_id, per_page, page)
+    })
+    .await
+}
+
+/// Get the release asset
+#[cfg(feature = "blocking")]
+pub fn get_release_asset(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    block_on(get_release_asset_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        asset_id,
+    ))
+}
+
+/// Get the release asset, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn get_release_asset_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    send_to_github_api_as_async(
+        github_client,
+        github_api_get_release_asset_async(github_owner_or_organization, repo, asset_id),
+    )
+    .await
+}
+
+/// Rename the release asset and optionally change the label
+#[cfg(feature = "blocking")]
+pub fn rename_release_asset(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+    new_name: &str,
+    label: Option<&str>,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    block_on(rename_release_asset_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        asset_id,
+        new_name,
+        label,
+    ))
+}
+
+/// Rename the release asset and optionally change the label, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn rename_release_asset_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+    new_name: &str,
+    label: Option<&str>,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    send_to_github_api_as_async(
+        github_client,
+        github_api_update_release_asset_async(github_owner_or_organization, repo, asset_id, new_name, label),
+    )
+    .await
+}
+
+/// Delete the release asset
+#[cfg(feature = "blocking")]
+pub fn delete_release_asset(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+) -> ResultWithGitHubLibError<()> {
+    block_on(delete_release_asset_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        asset_id,
+    ))
+}
+
+/// Delete the release asset, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn delete_release_asset_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    asset_id: i64,
+) -> ResultWithGitHubLibError<()> {
+    send_to_github_api_with_retry_async(
+        github_client,
+        github_api_delete_release_asset_async(github_owner_or_organization, repo, asset_id),
+    )
+    .await?
+    .into_result("delete_release_asset")?;
+    Ok(())
+}
+
+/// Upload the asset to the release and replace the existing asset with the same name
+///
+/// GitHub does not allow two assets with the same name in one release.
+/// The old asset is deleted first, so a rebuilt binary can be attached again to an existing release.
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub fn upload_or_replace_asset(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    path_to_file: &str,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    block_on_in_tokio_runtime(upload_or_replace_asset_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        release_id,
+        path_to_file,
+        default_upload_progress(),
+    ))?
+}
+
+/// Upload the asset to the release and replace the existing asset with the same name, async
+///
+/// Look at the upload_or_replace_asset() for details.
+/// The upload reports the progress to the callback like upload_asset_to_release_async().
+#[cfg(feature = "upload")]
+pub async fn upload_or_replace_asset_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    path_to_file: &str,
+    upload_progress: Option<std::sync::Arc<dyn UploadProgress>>,
+) -> ResultWithGitHubLibError<ReleaseAsset> {
+    let file_name = camino::Utf8Path::new(path_to_file)
+        .file_name()
+        .ok_or_else(|| GitHubLibError::ErrorFromString(format!("The path {path_to_file} does not contain a file name.")))?;
+    for asset in list_release_assets_async(github_client, github_owner_or_organization, repo, release_id).await? {
+        if asset.name == file_name {
+            println!("    {YELLOW}Deleting the old asset {file_name} from the GitHub release.{RESET}");
+            delete_release_asset_async(github_client, github_owner_or_organization, repo, asset.id).await?;
+        }
+    }
+    upload_asset_to_release_async(
+        github_client,
+        github_owner_or_organization,
+        repo,
+        release_id,
+        path_to_file,
+        upload_progress,
+    )
+    .await
+}
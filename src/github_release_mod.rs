@@ -0,0 +1,823 @@
+// github_release_mod.rs
+
+//! Release lifecycle: create, list, get by tag, update and delete releases.
+//!
+//! The github_api_* functions only create the RequestBuilder.
+//! The other functions send the request with the GitHubClient and return the typed result.
+
+#[cfg(feature = "blocking")]
+use crate::async_github_client_mod::BlockingGitHubClient;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::error_mod::ResultWithGitHubLibError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_endpoint_mod::github_endpoint;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_request_mod::GitHubRequest;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::send_to_github_api_as_async;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::Release;
+#[cfg(feature = "blocking")]
+use crate::pagination_mod::paginate;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::pagination_mod::paginate_async;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::pagination_mod::MAX_PER_PAGE;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::retry_mod::send_to_github_api_with_retry_async;
+#[cfg(feature = "blocking")]
+use crate::utils_mod::block_on;
+
+/// Options for the new release
+///
+/// The default is a published full release without generated release notes, like it was always.
+/// ```rust ignore
+/// let new_release_options = cgl::NewReleaseOptions::from_version(&version).draft(true).generate_release_notes(true);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewReleaseOptions {
+    pub draft: bool,
+    pub prerelease: bool,
+    /// GitHub generates the name and notes from the merged pull requests, the body is prepended
+    pub generate_release_notes: bool,
+    /// None means the GitHub default: the latest by date and version
+    pub make_latest: Option<MakeLatest>,
+    /// creates a discussion in this category for the release
+    pub discussion_category_name: Option<String>,
+}
+
+/// Should the new release become the latest release
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MakeLatest {
+    True,
+    False,
+    /// the latest by date and version, like GitHub did before
+    Legacy,
+}
+
+impl MakeLatest {
+    /// The string value for GitHub API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MakeLatest::True => "true",
+            MakeLatest::False => "false",
+            MakeLatest::Legacy => "legacy",
+        }
+    }
+}
+
+impl NewReleaseOptions {
+    /// Published full release
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prerelease if the version has a SemVer pre-release like `1.0.0-alpha.1` or `v1.0.0-rc.2`
+    ///
+    /// Use the version from Cargo.toml or the tag name.
+    pub fn from_version(version: &str) -> Self {
+        Self::new().prerelease(is_semver_prerelease(version))
+    }
+
+    /// Draft is not visible to the public
+    pub fn draft(mut self, draft: bool) -> Self {
+        self.draft = draft;
+        self
+    }
+
+    /// Prerelease is not production ready
+    pub fn prerelease(mut self, prerelease: bool) -> Self {
+        self.prerelease = prerelease;
+        self
+    }
+
+    /// GitHub generates the release notes
+    pub fn generate_release_notes(mut self, generate_release_notes: bool) -> Self {
+        self.generate_release_notes = generate_release_notes;
+        self
+    }
+
+    /// Set the new release as the latest or not
+    pub fn make_latest(mut self, make_latest: MakeLatest) -> Self {
+        self.make_latest = Some(make_latest);
+        self
+    }
+
+    /// Create a discussion in this existing category
+    pub fn discussion_category_name(mut self, discussion_category_name: &str) -> Self {
+        self.discussion_category_name = Some(discussion_category_name.to_string());
+        self
+    }
+}
+
+/// The version has a SemVer pre-release component
+///
+/// The leading `v` from the tag name is ignored. Not valid SemVer is never a prerelease.
+pub fn is_semver_prerelease(version: &str) -> bool {
+    match semver::Version::parse(version.trim_start_matches('v')) {
+        Ok(version) => !version.pre.is_empty(),
+        Err(_) => false,
+    }
+}
+
+/// Fields to update in an existing release
+///
+/// Only the fields that are set will be sent to GitHub.
+/// ```rust ignore
+/// let update_release = cgl::UpdateRelease::new().body("Fixed typo").draft(false);
+/// ```
+#[derive(serde::Serialize, Debug, Clone, Default, PartialEq)]
+pub struct UpdateRelease {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_commitish: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prerelease: Option<bool>,
+}
+
+impl UpdateRelease {
+    /// Nothing to update yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New tag name
+    pub fn tag_name(mut self, tag_name: &str) -> Self {
+        self.tag_name = Some(tag_name.to_string());
+        self
+    }
+
+    /// New branch or commit for the tag
+    pub fn target_commitish(mut self, target_commitish: &str) -> Self {
+        self.target_commitish = Some(target_commitish.to_string());
+        self
+    }
+
+    /// New release name
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// New release notes in markdown
+    pub fn body(mut self, body: &str) -> Self {
+        self.body = Some(body.to_string());
+        self
+    }
+
+    /// Draft or published
+    pub fn draft(mut self, draft: bool) -> Self {
+        self.draft = Some(draft);
+        self
+    }
+
+    /// Prerelease or full release
+    pub fn prerelease(mut self, prerelease: bool) -> Self {
+        self.prerelease = Some(prerelease);
+        self
+    }
+}
+
+/// Create new release on Github
+///
+/// The release is marked as prerelease if the version in tag_name_version has a SemVer pre-release like `v1.0.0-alpha.1`.
+#[cfg(feature = "blocking")]
+pub fn github_api_create_new_release(
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name_version: &str,
+    name: &str,
+    branch: &str,
+    body_md_text: &str,
+) -> reqwest::blocking::RequestBuilder {
+    let new_release_options = NewReleaseOptions::from_version(tag_name_version);
+    github_api_create_new_release_with_options(
+        github_owner_or_organization,
+        repo,
+        tag_name_version,
+        name,
+        branch,
+        body_md_text,
+        &new_release_options,
+    )
+}
+
+/// Create new release on Github, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+// create_or_update_release_async() uses the _with_options_async() variant, so only the async API exports this one
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+pub fn github_api_create_new_release_async(
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name_version: &str,
+    name: &str,
+    branch: &str,
+    body_md_text: &str,
+) -> reqwest::RequestBuilder {
+    let new_release_options = NewReleaseOptions::from_version(tag_name_version);
+    github_api_create_new_release_with_options_async(
+        github_owner_or_organization,
+        repo,
+        tag_name_version,
+        name,
+        branch,
+        body_md_text,
+        &new_release_options,
+    )
+}
+
+/// Create new release on Github with options for draft, prerelease and release notes
+#[cfg(feature = "blocking")]
+pub fn github_api_create_new_release_with_options(
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name_version: &str,
+    name: &str,
+    branch: &str,
+    body_md_text: &str,
+    new_release_options: &NewReleaseOptions,
+) -> reqwest::blocking::RequestBuilder {
+    create_new_release_with_options_request(
+        github_owner_or_organization,
+        repo,
+        tag_name_version,
+        name,
+        branch,
+        body_md_text,
+        new_release_options,
+    )
+    .request_builder()
+}
+
+/// Create new release on Github with options for draft, prerelease and release notes, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_create_new_release_with_options_async(
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name_version: &str,
+    name: &str,
+    branch: &str,
+    body_md_text: &str,
+    new_release_options: &NewReleaseOptions,
+) -> reqwest::RequestBuilder {
+    create_new_release_with_options_request(
+        github_owner_or_organization,
+        repo,
+        tag_name_version,
+        name,
+        branch,
+        body_md_text,
+        new_release_options,
+    )
+    .async_request_builder()
+}
+
+/// Describe the request for github_api_create_new_release_with_options()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn create_new_release_with_options_request(
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name_version: &str,
+    name: &str,
+    branch: &str,
+    body_md_text: &str,
+    new_release_options: &NewReleaseOptions,
+) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#create-a-release
+    Request like :
+    curl -L \
+    -X POST \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>"\
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/github_owner/REPO/releases \
+    -d '
+    {
+        "tag_name":"v1.0.0",
+        "target_commitish":"master",
+        "name":"v1.0.0",
+        "body":"Description of the release",
+        "draft":false,
+        "prerelease":false,
+        "generate_release_notes":false,
+        "make_latest":"true",
+        "discussion_category_name":"Announcements"
+    }'
+
+    Response (short)
+    {
+    "id": 1,
+    ...
+    }
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let releases_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases");
+    let mut body = serde_json::json!({
+        "tag_name": tag_name_version,
+        "target_commitish":branch,
+        "name":name,
+        "body":body_md_text,
+        "draft":new_release_options.draft,
+        "prerelease":new_release_options.prerelease,
+        "generate_release_notes":new_release_options.generate_release_notes,
+    });
+    if let Some(make_latest) = &new_release_options.make_latest {
+        body["make_latest"] = serde_json::json!(make_latest.as_str());
+    }
+    if let Some(discussion_category_name) = &new_release_options.discussion_category_name {
+        body["discussion_category_name"] = serde_json::json!(discussion_category_name);
+    }
+    GitHubRequest::from_url(reqwest::Method::POST, &releases_url, Some(body))
+}
+
+/// GitHub API list releases, one page
+#[cfg(feature = "blocking")]
+pub fn github_api_list_releases(
+    github_owner_or_organization: &str,
+    repo: &str,
+    per_page: u32,
+    page: u32,
+) -> reqwest::blocking::RequestBuilder {
+    list_releases_request(github_owner_or_organization, repo, per_page, page).request_builder()
+}
+
+/// GitHub API list releases, one page, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_list_releases_async(github_owner_or_organization: &str, repo: &str, per_page: u32, page: u32) -> reqwest::RequestBuilder {
+    list_releases_request(github_owner_or_organization, repo, per_page, page).async_request_builder()
+}
+
+/// Describe the request for github_api_list_releases()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn list_releases_request(github_owner_or_organization: &str, repo: &str, per_page: u32, page: u32) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#list-releases
+
+    curl -L \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    "https://api.github.com/repos/OWNER/REPO/releases?per_page=30&page=1"
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let releases_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases?per_page={per_page}&page={page}");
+    GitHubRequest::from_url(reqwest::Method::GET, &releases_url, None)
+}
+
+/// GitHub API get a release by id
+#[cfg(feature = "blocking")]
+pub fn github_api_get_release(github_owner_or_organization: &str, repo: &str, release_id: i64) -> reqwest::blocking::RequestBuilder {
+    get_release_request(github_owner_or_organization, repo, release_id).request_builder()
+}
+
+/// GitHub API get a release by id, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_get_release_async(github_owner_or_organization: &str, repo: &str, release_id: i64) -> reqwest::RequestBuilder {
+    get_release_request(github_owner_or_organization, repo, release_id).async_request_builder()
+}
+
+/// Describe the request for github_api_get_release()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn get_release_request(github_owner_or_organization: &str, repo: &str, release_id: i64) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#get-a-release
+
+    curl -L \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/OWNER/REPO/releases/RELEASE_ID
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let release_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/{release_id}");
+    GitHubRequest::from_url(reqwest::Method::GET, &release_url, None)
+}
+
+/// GitHub API get a release by tag name
+///
+/// Draft releases are not returned by this endpoint.
+#[cfg(feature = "blocking")]
+pub fn github_api_get_release_by_tag(github_owner_or_organization: &str, repo: &str, tag_name: &str) -> reqwest::blocking::RequestBuilder {
+    get_release_by_tag_request(github_owner_or_organization, repo, tag_name).request_builder()
+}
+
+/// GitHub API get a release by tag name, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_get_release_by_tag_async(github_owner_or_organization: &str, repo: &str, tag_name: &str) -> reqwest::RequestBuilder {
+    get_release_by_tag_request(github_owner_or_organization, repo, tag_name).async_request_builder()
+}
+
+/// Describe the request for github_api_get_release_by_tag()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn get_release_by_tag_request(github_owner_or_organization: &str, repo: &str, tag_name: &str) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#get-a-release-by-tag-name
+
+    curl -L \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/OWNER/REPO/releases/tags/TAG
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let release_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/tags/{tag_name}");
+    GitHubRequest::from_url(reqwest::Method::GET, &release_url, None)
+}
+
+/// GitHub API get the latest release
+///
+/// The latest release is the most recent non-prerelease, non-draft release.
+#[cfg(feature = "blocking")]
+pub fn github_api_get_latest_release(github_owner_or_organization: &str, repo: &str) -> reqwest::blocking::RequestBuilder {
+    get_latest_release_request(github_owner_or_organization, repo).request_builder()
+}
+
+/// GitHub API get the latest release, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_get_latest_release_async(github_owner_or_organization: &str, repo: &str) -> reqwest::RequestBuilder {
+    get_latest_release_request(github_owner_or_organization, repo).async_request_builder()
+}
+
+/// Describe the request for github_api_get_latest_release()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn get_latest_release_request(github_owner_or_organization: &str, repo: &str) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#get-the-latest-release
+
+    curl -L \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/OWNER/REPO/releases/latest
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let release_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/latest");
+    GitHubRequest::from_url(reqwest::Method::GET, &release_url, None)
+}
+
+/// GitHub API update a release
+#[cfg(feature = "blocking")]
+pub fn github_api_update_release(
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    update_release: &UpdateRelease,
+) -> reqwest::blocking::RequestBuilder {
+    update_release_request(github_owner_or_organization, repo, release_id, update_release).request_builder()
+}
+
+/// GitHub API update a release, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_update_release_async(
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    update_release: &UpdateRelease,
+) -> reqwest::RequestBuilder {
+    update_release_request(github_owner_or_organization, repo, release_id, update_release).async_request_builder()
+}
+
+/// Describe the request for github_api_update_release()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn update_release_request(
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    update_release: &UpdateRelease,
+) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#update-a-release
+
+    curl -L \
+    -X PATCH \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/OWNER/REPO/releases/RELEASE_ID \
+    -d '{"tag_name":"v1.0.0","target_commitish":"master","name":"v1.0.0","body":"Description of the release","draft":false,"prerelease":false}'
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let release_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/{release_id}");
+    let body = serde_json::json!(update_release);
+    GitHubRequest::from_url(reqwest::Method::PATCH, &release_url, Some(body))
+}
+
+/// GitHub API delete a release
+///
+/// The git tag is not deleted.
+/// The response is `204 No Content` without a json body.
+#[cfg(feature = "blocking")]
+pub fn github_api_delete_release(github_owner_or_organization: &str, repo: &str, release_id: i64) -> reqwest::blocking::RequestBuilder {
+    delete_release_request(github_owner_or_organization, repo, release_id).request_builder()
+}
+
+/// GitHub API delete a release, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_delete_release_async(github_owner_or_organization: &str, repo: &str, release_id: i64) -> reqwest::RequestBuilder {
+    delete_release_request(github_owner_or_organization, repo, release_id).async_request_builder()
+}
+
+/// Describe the request for github_api_delete_release()
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn delete_release_request(github_owner_or_organization: &str, repo: &str, release_id: i64) -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#delete-a-release
+
+    curl -L \
+    -X DELETE \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/repos/OWNER/REPO/releases/RELEASE_ID
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let release_url = format!("{api_base_url}/repos/{github_owner_or_organization}/{repo}/releases/{release_id}");
+    GitHubRequest::from_url(reqwest::Method::DELETE, &release_url, None)
+}
+
+/// List all the releases, page after page
+#[cfg(feature = "blocking")]
+pub fn list_releases(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+) -> ResultWithGitHubLibError<Vec<Release>> {
+    paginate(github_client, MAX_PER_PAGE, |per_page, page| {
+        github_api_list_releases(github_owner_or_organization, repo, per_page, page)
+    })
+    .collect()
+}
+
+/// List all the releases, page after page, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn list_releases_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+) -> ResultWithGitHubLibError<Vec<Release>> {
+    paginate_async(github_client, MAX_PER_PAGE, |per_page, page| {
+        github_api_list_releases_async(github_owner_or_organization, repo, per_page, page)
+    })
+    .await
+}
+
+/// Get the release by id
+#[cfg(feature = "blocking")]
+pub fn get_release(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+) -> ResultWithGitHubLibError<Release> {
+    block_on(get_release_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        release_id,
+    ))
+}
+
+/// Get the release by id, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn get_release_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+) -> ResultWithGitHubLibError<Release> {
+    send_to_github_api_as_async(
+        github_client,
+        github_api_get_release_async(github_owner_or_organization, repo, release_id),
+    )
+    .await
+}
+
+/// Get the release by tag name, None if it does not exist
+#[cfg(feature = "blocking")]
+pub fn get_release_by_tag(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name: &str,
+) -> ResultWithGitHubLibError<Option<Release>> {
+    block_on(get_release_by_tag_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        tag_name,
+    ))
+}
+
+/// Get the release by tag name, None if it does not exist, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn get_release_by_tag_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name: &str,
+) -> ResultWithGitHubLibError<Option<Release>> {
+    not_found_to_none(
+        send_to_github_api_as_async(
+            github_client,
+            github_api_get_release_by_tag_async(github_owner_or_organization, repo, tag_name),
+        )
+        .await,
+    )
+}
+
+/// Get the latest release, None if there is no published release
+#[cfg(feature = "blocking")]
+pub fn get_latest_release(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+) -> ResultWithGitHubLibError<Option<Release>> {
+    block_on(get_latest_release_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+    ))
+}
+
+/// Get the latest release, None if there is no published release, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn get_latest_release_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+) -> ResultWithGitHubLibError<Option<Release>> {
+    not_found_to_none(
+        send_to_github_api_as_async(
+            github_client,
+            github_api_get_latest_release_async(github_owner_or_organization, repo),
+        )
+        .await,
+    )
+}
+
+/// Update the release and return the updated release
+#[cfg(feature = "blocking")]
+pub fn update_release(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    update_release: &UpdateRelease,
+) -> ResultWithGitHubLibError<Release> {
+    block_on(update_release_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        release_id,
+        update_release,
+    ))
+}
+
+/// Update the release and return the updated release, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn update_release_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+    update_release: &UpdateRelease,
+) -> ResultWithGitHubLibError<Release> {
+    send_to_github_api_as_async(
+        github_client,
+        github_api_update_release_async(github_owner_or_organization, repo, release_id, update_release),
+    )
+    .await
+}
+
+/// Delete the release, the git tag stays
+#[cfg(feature = "blocking")]
+pub fn delete_release(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+) -> ResultWithGitHubLibError<()> {
+    block_on(delete_release_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        release_id,
+    ))
+}
+
+/// Delete the release, the git tag stays, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn delete_release_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    release_id: i64,
+) -> ResultWithGitHubLibError<()> {
+    send_to_github_api_with_retry_async(
+        github_client,
+        github_api_delete_release_async(github_owner_or_organization, repo, release_id),
+    )
+    .await?
+    .into_result("delete_release")?;
+    Ok(())
+}
+
+/// Create a new release or update the existing release for this tag
+///
+/// Re-running the release task after a partial failure does not fail with `already_exists`.
+/// The existing release gets the new name and body. The release is found also if it is a draft.
+/// Returns the release id, so the upload of assets can continue.
+#[allow(clippy::too_many_arguments)]
+#[cfg(feature = "blocking")]
+pub fn create_or_update_release(
+    github_client: &impl SendToGitHubApi,
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name_version: &str,
+    name: &str,
+    branch: &str,
+    body_md_text: &str,
+    new_release_options: &NewReleaseOptions,
+) -> ResultWithGitHubLibError<i64> {
+    block_on(create_or_update_release_async(
+        &BlockingGitHubClient::new(github_client),
+        github_owner_or_organization,
+        repo,
+        tag_name_version,
+        name,
+        branch,
+        body_md_text,
+        new_release_options,
+    ))
+}
+
+/// Create a new release or update the existing release for this tag, async
+///
+/// Look at the create_or_update_release() for details.
+#[allow(clippy::too_many_arguments)]
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn create_or_update_release_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_owner_or_organization: &str,
+    repo: &str,
+    tag_name_version: &str,
+    name: &str,
+    branch: &str,
+    body_md_text: &str,
+    new_release_options: &NewReleaseOptions,
+) -> ResultWithGitHubLibError<i64> {
+    // the endpoint for the tag does not return draft releases, so they must be searched in the list
+    let existing_release = match get_release_by_tag_async(github_client, github_owner_or_organization, repo, tag_name_version).await? {
+        Some(release) => Some(release),
+        None => list_releases_async(github_client, github_owner_or_organization, repo)
+            .await?
+            .into_iter()
+            .find(|release| release.tag_name == tag_name_version),
+    };
+
+    match existing_release {
+        Some(release) => {
+            let update = UpdateRelease::new().name(name).body(body_md_text);
+            let release = update_release_async(github_client, github_owner_or_organization, repo, release.id, &update).await?;
+            Ok(release.id)
+        }
+        None => {
+            let release: Release = send_to_github_api_as_async(
+                github_client,
+                github_api_create_new_release_with_options_async(
+                    github_owner_or_organization,
+                    repo,
+                    tag_name_version,
+                    name,
+                    branch,
+                    body_md_text,
+                    new_release_options,
+                ),
+            )
+            .await?;
+            Ok(release.id)
+        }
+    }
+}
+
+/// The GitHub error `Not Found` becomes None
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn not_found_to_none<T>(result: ResultWithGitHubLibError<T>) -> ResultWithGitHubLibError<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.is_not_found() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,269 @@
+// github_request_mod.rs
+
+//! Declarative description of every request to GitHub API.
+//!
+//! Every github_api_* function first describes the request as a GitHubRequest
+//! and then creates the blocking or the async RequestBuilder from it.
+//! The library hands the GitHubRequest to the GitHubClient before sending the request.
+//! The client holds the secret_token and can decide which operations it allows.
+//! This hook is only advisory: the RequestBuilder from a github_api_* function can be sent without it.
+//! With the GitHubRequestAllowlist inside send_to_github_api() the automation task gets the least privilege it needs:
+//! ```rust ignore
+//! impl cgl::SendToGitHubApi for GitHubClient {
+//!     fn send_to_github_api(&self, req: reqwest::blocking::RequestBuilder) -> serde_json::Value {
+//!         // every request goes through here, so no request is sent without the check
+//!         let github_request = cgl::GitHubRequest::from_request_builder(&req).unwrap();
+//!         cgl::GitHubRequestAllowlist::new(&["get_release_by_tag", "create_release", "upload_release_asset"])
+//!             .check(&github_request)
+//!             .unwrap();
+//!         let req = req.bearer_auth(self.decrypt_secret_token_in_memory().expose_secret());
+//!         ...
+//!     }
+//!     ...
+//! }
+//! ```
+//! The upload request has a stream body that cannot be cloned, so upload_to_github() uses GitHubRequest::split_async_request_builder(req).
+//! The same check goes into send_to_github_api_with_response(), when the client implements it.
+
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::github_api_request_builder;
+use crate::auto_github_api_mod::github_api_request_builder_async;
+use crate::error_mod::GitHubLibError;
+use crate::error_mod::ResultWithGitHubLibError;
+use crate::github_endpoint_mod::github_endpoint;
+
+/// Known operations: method, path template and operation name
+///
+/// The segment in curly brackets matches any one segment. The first match wins.
+const OPERATIONS: &[(&str, &str, &str)] = &[
+    ("GET", "/user", "get_authenticated_user"),
+    ("POST", "/user/repos", "create_user_repository"),
+    ("GET", "/orgs/{org}", "get_organization"),
+    ("POST", "/orgs/{org}/repos", "create_organization_repository"),
+    ("GET", "/rate_limit", "get_rate_limit"),
+    ("GET", "/repos/{owner}/{repo}", "get_repository"),
+    ("PATCH", "/repos/{owner}/{repo}", "update_repository"),
+    ("DELETE", "/repos/{owner}/{repo}", "delete_repository"),
+    ("PUT", "/repos/{owner}/{repo}/topics", "replace_all_topics"),
+    ("POST", "/repos/{owner}/{repo}/pages", "create_github_pages_site"),
+    ("POST", "/repos/{owner}/{repo}/generate", "create_repository_from_template"),
+    ("GET", "/repos/{owner}/{repo}/releases", "list_releases"),
+    ("POST", "/repos/{owner}/{repo}/releases", "create_release"),
+    ("GET", "/repos/{owner}/{repo}/releases/latest", "get_latest_release"),
+    ("GET", "/repos/{owner}/{repo}/releases/tags/{tag}", "get_release_by_tag"),
+    ("GET", "/repos/{owner}/{repo}/releases/assets/{asset_id}", "get_release_asset"),
+    ("PATCH", "/repos/{owner}/{repo}/releases/assets/{asset_id}", "update_release_asset"),
+    ("DELETE", "/repos/{owner}/{repo}/releases/assets/{asset_id}", "delete_release_asset"),
+    ("GET", "/repos/{owner}/{repo}/releases/{release_id}", "get_release"),
+    ("PATCH", "/repos/{owner}/{repo}/releases/{release_id}", "update_release"),
+    ("DELETE", "/repos/{owner}/{repo}/releases/{release_id}", "delete_release"),
+    ("GET", "/repos/{owner}/{repo}/releases/{release_id}/assets", "list_release_assets"),
+    ("POST", "/repos/{owner}/{repo}/releases/{release_id}/assets", "upload_release_asset"),
+];
+
+/// Description of one request to GitHub API
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubRequest {
+    /// the operation name like `create_release` or `METHOD /path` for unknown endpoints
+    pub operation: String,
+    pub method: reqwest::Method,
+    /// the path without the base url of the endpoint and without the query, like `/repos/owner/repo/releases`
+    pub path: String,
+    /// the full url with the query
+    pub url: String,
+    /// the json body, None if there is no body or it is not json (like the stream for uploads)
+    pub body: Option<serde_json::Value>,
+}
+
+impl GitHubRequest {
+    /// Describe the request from the method, url and body
+    pub fn new(method: reqwest::Method, url: &url::Url, body: Option<serde_json::Value>) -> Self {
+        let path = path_without_base_url(url);
+        let operation = operation_name(&method, &path);
+        GitHubRequest {
+            operation,
+            method,
+            path,
+            url: url.to_string(),
+            body,
+        }
+    }
+
+    /// Describe the request from the method, url string and body
+    ///
+    /// The url that cannot be parsed is used as the path. The request builder will return the error when sending.
+    pub fn from_url(method: reqwest::Method, url: &str, body: Option<serde_json::Value>) -> Self {
+        match url::Url::parse(url) {
+            Ok(parsed_url) => Self::new(method, &parsed_url, body),
+            Err(_) => GitHubRequest {
+                operation: format!("{method} {url}"),
+                method,
+                path: url.to_string(),
+                url: url.to_string(),
+                body,
+            },
+        }
+    }
+
+    /// Describe the blocking request
+    ///
+    /// The request with a stream body cannot be cloned, use split_request_builder() for it.
+    #[cfg(feature = "blocking")]
+    pub fn from_request_builder(req: &reqwest::blocking::RequestBuilder) -> ResultWithGitHubLibError<Self> {
+        let request = req
+            .try_clone()
+            .ok_or(GitHubLibError::ErrorFromStr("The request with a stream body cannot be described."))?
+            .build()
+            .map_err(|e| GitHubLibError::ErrorFromString(e.to_string()))?;
+        Ok(Self::new(
+            request.method().clone(),
+            request.url(),
+            json_body(request.body().and_then(|body| body.as_bytes())),
+        ))
+    }
+
+    /// Describe the async request
+    ///
+    /// The request with a stream body cannot be cloned, use split_async_request_builder() for it.
+    pub fn from_async_request_builder(req: &reqwest::RequestBuilder) -> ResultWithGitHubLibError<Self> {
+        let request = req
+            .try_clone()
+            .ok_or(GitHubLibError::ErrorFromStr("The request with a stream body cannot be described."))?
+            .build()
+            .map_err(|e| GitHubLibError::ErrorFromString(e.to_string()))?;
+        Ok(Self::new(
+            request.method().clone(),
+            request.url(),
+            json_body(request.body().and_then(|body| body.as_bytes())),
+        ))
+    }
+
+    /// Describe the blocking request with any body and return the same request builder
+    ///
+    /// The method and url exist for every request. The stream body is described as no body.
+    #[cfg(feature = "blocking")]
+    pub fn split_request_builder(
+        req: reqwest::blocking::RequestBuilder,
+    ) -> ResultWithGitHubLibError<(Self, reqwest::blocking::RequestBuilder)> {
+        let (client, request) = req.build_split();
+        let request = request.map_err(|e| GitHubLibError::ErrorFromString(e.to_string()))?;
+        let github_request = Self::new(
+            request.method().clone(),
+            request.url(),
+            json_body(request.body().and_then(|body| body.as_bytes())),
+        );
+        Ok((github_request, reqwest::blocking::RequestBuilder::from_parts(client, request)))
+    }
+
+    /// Describe the async request with any body and return the same request builder
+    ///
+    /// The method and url exist for every request. The stream body is described as no body.
+    pub fn split_async_request_builder(req: reqwest::RequestBuilder) -> ResultWithGitHubLibError<(Self, reqwest::RequestBuilder)> {
+        let (client, request) = req.build_split();
+        let request = request.map_err(|e| GitHubLibError::ErrorFromString(e.to_string()))?;
+        let github_request = Self::new(
+            request.method().clone(),
+            request.url(),
+            json_body(request.body().and_then(|body| body.as_bytes())),
+        );
+        Ok((github_request, reqwest::RequestBuilder::from_parts(client, request)))
+    }
+
+    /// The blocking request builder with the common headers and the json body
+    #[cfg(feature = "blocking")]
+    pub fn request_builder(&self) -> reqwest::blocking::RequestBuilder {
+        let req = github_api_request_builder(self.method.clone(), &self.url);
+        match &self.body {
+            Some(body) => req.body(body.to_string()),
+            None => req,
+        }
+    }
+
+    /// The async request builder with the common headers and the json body
+    pub fn async_request_builder(&self) -> reqwest::RequestBuilder {
+        let req = github_api_request_builder_async(self.method.clone(), &self.url);
+        match &self.body {
+            Some(body) => req.body(body.to_string()),
+            None => req,
+        }
+    }
+}
+
+/// The json body, None if there is no body or it is not json
+fn json_body(body: Option<&[u8]>) -> Option<serde_json::Value> {
+    body.and_then(|body| serde_json::from_slice(body).ok())
+}
+
+/// The path of the url without the base url of the endpoint
+///
+/// GitHub Enterprise Server has the base url path like `/api/v3` or `/api/uploads`.
+fn path_without_base_url(url: &url::Url) -> String {
+    let github_endpoint = github_endpoint();
+    let url_without_query = format!("{}://{}{}", url.scheme(), url.host_str().unwrap_or_default(), url.path());
+    for base_url in [&github_endpoint.api_base_url, &github_endpoint.uploads_base_url] {
+        let base_url = base_url.trim_end_matches('/');
+        if let Some(path) = url_without_query.strip_prefix(base_url) {
+            if path.starts_with('/') {
+                return path.to_string();
+            }
+        }
+    }
+    url.path().to_string()
+}
+
+/// The operation name from the table of known operations or `METHOD /path`
+fn operation_name(method: &reqwest::Method, path: &str) -> String {
+    let path_segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    OPERATIONS
+        .iter()
+        .find(|(operation_method, path_template, _)| {
+            let template_segments: Vec<&str> = path_template.trim_matches('/').split('/').collect();
+            *operation_method == method.as_str()
+                && template_segments.len() == path_segments.len()
+                && template_segments
+                    .iter()
+                    .zip(path_segments.iter())
+                    .all(|(template, segment)| template.starts_with('{') || template == segment)
+        })
+        .map(|(_, _, operation)| operation.to_string())
+        .unwrap_or_else(|| format!("{method} {path}"))
+}
+
+/// List of allowed operations for the least privilege of the GitHubClient
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GitHubRequestAllowlist {
+    operations: Vec<String>,
+}
+
+impl GitHubRequestAllowlist {
+    /// Allow only these operation names
+    pub fn new(operations: &[&str]) -> Self {
+        GitHubRequestAllowlist {
+            operations: operations.iter().map(|operation| operation.to_string()).collect(),
+        }
+    }
+
+    /// Allow one more operation name
+    pub fn allow(mut self, operation: &str) -> Self {
+        self.operations.push(operation.to_string());
+        self
+    }
+
+    /// Is the operation of this request allowed
+    pub fn is_allowed(&self, github_request: &GitHubRequest) -> bool {
+        self.operations.iter().any(|operation| operation == &github_request.operation)
+    }
+
+    /// Return the error if the operation of this request is not allowed
+    pub fn check(&self, github_request: &GitHubRequest) -> ResultWithGitHubLibError<()> {
+        if self.is_allowed(github_request) {
+            Ok(())
+        } else {
+            Err(GitHubLibError::RequestNotAllowedError {
+                operation: github_request.operation.clone(),
+                method: github_request.method.to_string(),
+                path: github_request.path.clone(),
+            })
+        }
+    }
+}
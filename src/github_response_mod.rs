@@ -0,0 +1,142 @@
+// github_response_mod.rs
+
+//! The response from GitHub API with the status code, headers and json body.
+//!
+//! The json body alone cannot tell 201 Created from 422 Unprocessable Entity
+//! and the endpoints with 204 No Content have no json at all.
+//! The headers contain Location, ETag, Link for pagination and X-RateLimit-* for rate limits.
+//! The GitHubClient implements SendToGitHubApi::send_to_github_api_with_response() with GitHubResponse::from_blocking_response().
+//! The old GitHubClient with only send_to_github_api() still works with the default adapter GitHubResponse::from_json().
+
+use crate::error_mod::check_github_api_response;
+use crate::error_mod::GitHubLibError;
+use crate::error_mod::ResultWithGitHubLibError;
+use crate::pagination_mod::next_page_url_from_link_header;
+use crate::rate_limit_mod::retry_after_from_headers;
+use crate::rate_limit_mod::RateLimit;
+
+/// Response from GitHub API
+#[derive(Debug, Clone, Default)]
+pub struct GitHubResponse {
+    /// HTTP status code like 200, 201, 204 or 404
+    pub status: u16,
+    pub headers: reqwest::header::HeaderMap,
+    /// json null for the empty body like 204 No Content
+    pub body: serde_json::Value,
+}
+
+impl GitHubResponse {
+    /// New response from the parts
+    pub fn new(status: u16, headers: reqwest::header::HeaderMap, body: serde_json::Value) -> Self {
+        GitHubResponse { status, headers, body }
+    }
+
+    /// Response from the json body only, without headers
+    ///
+    /// This is the adapter for the GitHubClient that returns only the json.
+    /// The status is guessed: the GitHub error json contains the `message` and newer also the `status` as a string.
+    /// An error without the status becomes 400, the null body becomes 204 and anything else 200.
+    pub fn from_json(body: serde_json::Value) -> Self {
+        let status = if let Some(status) = body.get("status").and_then(|s| s.as_str()).and_then(|s| s.parse::<u16>().ok()) {
+            status
+        } else if body.get("message").is_some_and(|m| m.is_string()) {
+            400
+        } else if body.is_null() {
+            204
+        } else {
+            200
+        };
+        GitHubResponse {
+            status,
+            headers: reqwest::header::HeaderMap::new(),
+            body,
+        }
+    }
+
+    /// Response from the reqwest blocking response
+    ///
+    /// The empty body becomes json null.
+    #[cfg(feature = "blocking")]
+    pub fn from_blocking_response(response: reqwest::blocking::Response) -> ResultWithGitHubLibError<Self> {
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let text = response.text().map_err(|e| GitHubLibError::ErrorFromString(e.to_string()))?;
+        Ok(GitHubResponse {
+            status,
+            headers,
+            body: body_from_text(&text)?,
+        })
+    }
+
+    /// Response from the reqwest async response
+    ///
+    /// The empty body becomes json null.
+    pub async fn from_response(response: reqwest::Response) -> ResultWithGitHubLibError<Self> {
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let text = response.text().await.map_err(|e| GitHubLibError::ErrorFromString(e.to_string()))?;
+        Ok(GitHubResponse {
+            status,
+            headers,
+            body: body_from_text(&text)?,
+        })
+    }
+
+    /// Status 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Get the value of a header by name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// The rate limit from the `X-RateLimit-*` headers
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        RateLimit::from_headers(&self.headers)
+    }
+
+    /// The wait from the `Retry-After` header
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        retry_after_from_headers(&self.headers)
+    }
+
+    /// The url of the next page from the `Link` header
+    pub fn next_page_url(&self) -> Option<String> {
+        self.header("link").and_then(next_page_url_from_link_header)
+    }
+
+    /// The json body or the error with the status code and the GitHub error message
+    pub fn into_result(self, operation: &str) -> ResultWithGitHubLibError<serde_json::Value> {
+        let is_success = self.is_success();
+        match check_github_api_response(operation, self.body) {
+            Ok(body) if is_success => Ok(body),
+            Ok(_) => Err(GitHubLibError::HttpStatusError {
+                operation: operation.to_string(),
+                status: self.status,
+            }),
+            Err(GitHubLibError::GitHubApiError {
+                operation,
+                status: None,
+                message,
+                errors,
+            }) => Err(GitHubLibError::GitHubApiError {
+                operation,
+                status: Some(self.status),
+                message,
+                errors,
+            }),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Parse the body text as json, the empty body is json null
+fn body_from_text(text: &str) -> ResultWithGitHubLibError<serde_json::Value> {
+    if text.trim().is_empty() {
+        Ok(serde_json::Value::Null)
+    } else {
+        Ok(serde_json::from_str(text)?)
+    }
+}
@@ -0,0 +1,199 @@
+// github_types_mod.rs
+
+//! Strongly typed structs for the json responses from GitHub API.
+//!
+//! Only the fields used in automation tasks are here. The json contains many more fields.
+//! The important fields like id and name are mandatory, so a change in the json shape returns an error.
+//! The less important fields are Option or have a default value.
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+use crate::error_mod::check_github_api_response;
+use crate::error_mod::ResultWithGitHubLibError;
+use crate::github_response_mod::GitHubResponse;
+#[cfg(feature = "blocking")]
+use crate::retry_mod::send_to_github_api_with_retry;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::retry_mod::send_to_github_api_with_retry_async;
+
+/// GitHub user like the authenticated user or the owner of a repository
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct User {
+    pub login: String,
+    pub id: i64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    /// User or Organization
+    #[serde(default, rename = "type")]
+    pub user_type: Option<String>,
+}
+
+/// GitHub organization
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct Organization {
+    pub login: String,
+    pub id: i64,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+}
+
+/// GitHub repository
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct Repository {
+    pub id: i64,
+    pub name: String,
+    pub full_name: String,
+    #[serde(default)]
+    pub owner: Option<User>,
+    /// the description is null if it was never set
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub default_branch: Option<String>,
+    #[serde(default)]
+    pub has_issues: bool,
+    #[serde(default)]
+    pub has_projects: bool,
+    #[serde(default)]
+    pub has_wiki: bool,
+    #[serde(default)]
+    pub has_discussions: bool,
+    /// public, private or internal
+    #[serde(default)]
+    pub visibility: Option<String>,
+    /// the merge settings are returned only to the users with admin rights
+    #[serde(default)]
+    pub allow_squash_merge: Option<bool>,
+    #[serde(default)]
+    pub allow_merge_commit: Option<bool>,
+    #[serde(default)]
+    pub allow_rebase_merge: Option<bool>,
+    #[serde(default)]
+    pub delete_branch_on_merge: Option<bool>,
+}
+
+/// GitHub release
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct Release {
+    pub id: i64,
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub target_commitish: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub upload_url: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// Asset attached to a GitHub release
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct ReleaseAsset {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// uploaded or open
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub download_count: u64,
+    #[serde(default)]
+    pub browser_download_url: Option<String>,
+}
+
+/// GitHub repository topics
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct Topics {
+    pub names: Vec<String>,
+}
+
+/// Deserialize the json response from GitHub API into a struct
+///
+/// First it checks if the response is a GitHub error with `message` and `errors`.
+pub fn github_api_response_to<T: serde::de::DeserializeOwned>(
+    operation: &str,
+    json_value: serde_json::Value,
+) -> ResultWithGitHubLibError<T> {
+    let json_value = check_github_api_response(operation, json_value)?;
+    Ok(serde_json::from_value(json_value)?)
+}
+
+/// Deserialize the response from GitHub API into a struct
+///
+/// The error status code returns an error. The empty body of 204 No Content is json null, so it deserializes into `()`.
+pub fn github_response_to<T: serde::de::DeserializeOwned>(operation: &str, github_response: GitHubResponse) -> ResultWithGitHubLibError<T> {
+    let json_value = github_response.into_result(operation)?;
+    Ok(serde_json::from_value(json_value)?)
+}
+
+/// Send the request to GitHub API and deserialize the response into a struct
+///
+/// The operation for the error message is the method and the url path, like `GET /repos/owner/repo`.
+#[cfg(feature = "blocking")]
+pub fn send_to_github_api_as<T: serde::de::DeserializeOwned>(
+    github_client: &impl SendToGitHubApi,
+    req: reqwest::blocking::RequestBuilder,
+) -> ResultWithGitHubLibError<T> {
+    let operation = request_operation(&req);
+    github_response_to(&operation, send_to_github_api_with_retry(github_client, req)?)
+}
+
+/// Send the async request to GitHub API and deserialize the response into a struct
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn send_to_github_api_as_async<T: serde::de::DeserializeOwned>(
+    github_client: &impl SendToGitHubApiAsync,
+    req: reqwest::RequestBuilder,
+) -> ResultWithGitHubLibError<T> {
+    let operation = async_request_operation(&req);
+    github_response_to(&operation, send_to_github_api_with_retry_async(github_client, req).await?)
+}
+
+/// The method and the url path of the async request, like `GET /repos/owner/repo`
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) fn async_request_operation(req: &reqwest::RequestBuilder) -> String {
+    req.try_clone()
+        .and_then(|req| req.build().ok())
+        .map(|request| format!("{} {}", request.method(), request.url().path()))
+        .unwrap_or_default()
+}
+
+/// The method and the url path of the request, like `GET /repos/owner/repo`
+#[cfg(feature = "blocking")]
+pub(crate) fn request_operation(req: &reqwest::blocking::RequestBuilder) -> String {
+    req.try_clone()
+        .and_then(|req| req.build().ok())
+        .map(|request| format!("{} {}", request.method(), request.url().path()))
+        .unwrap_or_default()
+}
@@ -0,0 +1,180 @@
+// http_client_mod.rs
+
+//! Shared HTTP clients for all the requests from this library.
+//!
+//! The reqwest Client holds the connection pool, the TLS sessions and the configuration like timeouts and proxy.
+//! It is cheap to clone, because it is an Arc inside. The library keeps one blocking and one async client.
+//! By default they are created on the first use with the reqwest defaults.
+//! The automation task can set its own clients or a HttpClientConfig once at the start:
+//! ```rust ignore
+//! cgl::set_http_client_config(
+//!     &cgl::HttpClientConfig::new()
+//!         .timeout(std::time::Duration::from_secs(60))
+//!         .proxy_url("http://proxy.example.com:8080"),
+//! )?;
+//! ```
+
+use crate::error_mod::GitHubLibError;
+use crate::error_mod::ResultWithGitHubLibError;
+
+/// Configuration for the shared HTTP clients
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HttpClientConfig {
+    /// timeout for the whole request, None means no timeout for async and 30 seconds for blocking
+    pub timeout: Option<std::time::Duration>,
+    pub connect_timeout: Option<std::time::Duration>,
+    /// proxy for all the requests, like `http://proxy.example.com:8080`
+    pub proxy_url: Option<String>,
+    /// additional trusted root certificates in PEM format, like for GitHub Enterprise Server with a private CA
+    pub root_certificates_pem: Vec<Vec<u8>>,
+}
+
+impl HttpClientConfig {
+    /// Configuration with the reqwest defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Timeout for the whole request
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for the connect phase
+    pub fn connect_timeout(mut self, connect_timeout: std::time::Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Proxy for all the requests
+    pub fn proxy_url(mut self, proxy_url: &str) -> Self {
+        self.proxy_url = Some(proxy_url.to_string());
+        self
+    }
+
+    /// Add a trusted root certificate in PEM format
+    pub fn root_certificate_pem(mut self, root_certificate_pem: &[u8]) -> Self {
+        self.root_certificates_pem.push(root_certificate_pem.to_vec());
+        self
+    }
+
+    /// Build the blocking client with this configuration
+    ///
+    /// Do not call it inside an async runtime, the blocking client has its own runtime.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking_client(&self) -> ResultWithGitHubLibError<reqwest::blocking::Client> {
+        let mut client_builder = reqwest::blocking::Client::builder();
+        if let Some(timeout) = self.timeout {
+            client_builder = client_builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            client_builder = client_builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = self.proxy()? {
+            client_builder = client_builder.proxy(proxy);
+        }
+        for certificate in self.root_certificates()? {
+            client_builder = client_builder.add_root_certificate(certificate);
+        }
+        client_builder.build().map_err(http_client_error)
+    }
+
+    /// Build the async client with this configuration
+    pub fn build_async_client(&self) -> ResultWithGitHubLibError<reqwest::Client> {
+        let mut client_builder = reqwest::Client::builder();
+        if let Some(timeout) = self.timeout {
+            client_builder = client_builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            client_builder = client_builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = self.proxy()? {
+            client_builder = client_builder.proxy(proxy);
+        }
+        for certificate in self.root_certificates()? {
+            client_builder = client_builder.add_root_certificate(certificate);
+        }
+        client_builder.build().map_err(http_client_error)
+    }
+
+    /// The proxy for all the requests
+    fn proxy(&self) -> ResultWithGitHubLibError<Option<reqwest::Proxy>> {
+        self.proxy_url
+            .as_deref()
+            .map(reqwest::Proxy::all)
+            .transpose()
+            .map_err(http_client_error)
+    }
+
+    /// The additional trusted root certificates
+    fn root_certificates(&self) -> ResultWithGitHubLibError<Vec<reqwest::Certificate>> {
+        self.root_certificates_pem
+            .iter()
+            .map(|pem| reqwest::Certificate::from_pem(pem).map_err(http_client_error))
+            .collect()
+    }
+}
+
+/// The reqwest error from building the client
+fn http_client_error(error: reqwest::Error) -> GitHubLibError {
+    GitHubLibError::ErrorFromString(format!("Cannot build the HTTP client: {error}"))
+}
+
+/// The blocking client is stored for the whole library. None means it is not created yet.
+#[cfg(feature = "blocking")]
+static HTTP_CLIENT: std::sync::RwLock<Option<reqwest::blocking::Client>> = std::sync::RwLock::new(None);
+
+/// The async client for uploads is stored for the whole library. None means it is not created yet.
+static ASYNC_HTTP_CLIENT: std::sync::RwLock<Option<reqwest::Client>> = std::sync::RwLock::new(None);
+
+/// Set the blocking client for all the requests from this library
+#[cfg(feature = "blocking")]
+pub fn set_http_client(http_client: reqwest::blocking::Client) {
+    *HTTP_CLIENT.write().unwrap_or_else(|e| e.into_inner()) = Some(http_client);
+}
+
+/// Set the async client for all the uploads from this library
+pub fn set_async_http_client(async_http_client: reqwest::Client) {
+    *ASYNC_HTTP_CLIENT.write().unwrap_or_else(|e| e.into_inner()) = Some(async_http_client);
+}
+
+/// Build and set both the blocking and the async client from the configuration
+pub fn set_http_client_config(http_client_config: &HttpClientConfig) -> ResultWithGitHubLibError<()> {
+    #[cfg(feature = "blocking")]
+    let http_client = http_client_config.build_blocking_client()?;
+    let async_http_client = http_client_config.build_async_client()?;
+    #[cfg(feature = "blocking")]
+    set_http_client(http_client);
+    set_async_http_client(async_http_client);
+    Ok(())
+}
+
+/// Get the blocking client used for all the requests from this library
+///
+/// The default client is created on the first use and then reused.
+#[cfg(feature = "blocking")]
+pub fn http_client() -> reqwest::blocking::Client {
+    if let Some(http_client) = HTTP_CLIENT.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return http_client.clone();
+    }
+    HTTP_CLIENT
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(reqwest::blocking::Client::new)
+        .clone()
+}
+
+/// Get the async client used for all the uploads from this library
+///
+/// The default client is created on the first use and then reused.
+pub fn async_http_client() -> reqwest::Client {
+    if let Some(async_http_client) = ASYNC_HTTP_CLIENT.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return async_http_client.clone();
+    }
+    ASYNC_HTTP_CLIENT
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(reqwest::Client::new)
+        .clone()
+}
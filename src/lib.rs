@@ -87,6 +87,70 @@
 //! - `auto_github_create_new_release()` - creates new release on Github
 //! - `auto_github_upload_asset_to_release()` - add asset to the github release
 //!
+//! ## GitHub Enterprise Server
+//!
+//! By default all requests go to `https://api.github.com` and `https://uploads.github.com`.  
+//! For GitHub Enterprise Server or a local test server set the endpoint once at the start of the automation task:
+//!
+//! ```rust ignore
+//! cgl::set_github_endpoint(cgl::GitHubEndpoint::github_enterprise_server("ghe.corp"));
+//! ```
+//!
+//! The GitHubClient must then check the host with `cgl::github_endpoint().is_api_url(req.url())` instead of `api.github.com`.
+//!
+//! ## Async
+//!
+//! Applications that already run inside a tokio runtime cannot use `reqwest::blocking`.  
+//! They implement the trait `SendToGitHubApiAsync` and call the functions with the `_async` suffix:
+//!
+//! ```rust ignore
+//! let release_id = cgl::create_or_update_release_async(&github_client, owner, repo, &tag, &name, "main", &notes, &options).await?;
+//! ```
+//!
+//! The blocking functions are thin wrappers around the same async functions.
+//!
+//! ## Dry run
+//!
+//! Before touching a production repository, use the `DryRunGitHubClient` instead of the real GitHubClient.  
+//! It prints the method, url and json body of every mutating request and never sends anything:
+//!
+//! ```rust ignore
+//! cgl::description_and_topics_to_github(&cgl::DryRunGitHubClient::new().user_login("bestia-dev"));
+//! ```
+//!
+//! ## Cargo features
+//!
+//! All the features are enabled by default. Disable the default features to compile only what the automation task needs:
+//!
+//! ```toml
+//! cargo_auto_github_lib = { version = "1.1", default-features = false, features = ["blocking"] }
+//! ```
+//!
+//! - `blocking` - the blocking functions and the `SendToGitHubApi` trait with `reqwest::blocking`
+//! - `async` - the `_async` functions and the `SendToGitHubApiAsync` trait
+//! - `upload` - upload of release assets with tokio and the progress bar
+//! - `interactive` - questions in the terminal with the `InquirePrompter` like `new_remote_github_repository()`
+//!
+//! ## Repository settings in Cargo.toml
+//!
+//! The settings of the GitHub repository live next to the crate manifest in the `[package.metadata.github]` table:
+//!
+//! ```toml
+//! [package.metadata.github]
+//! visibility = "public"
+//! has_discussions = true
+//! default_branch = "main"
+//! pages = { build_type = "workflow", branch = "main", path = "/docs" }
+//! delete_branch_on_merge = true
+//! topics = ["automation"]
+//! ```
+//!
+//! `new_remote_github_repository()` creates the repository with these settings and `description_and_topics_to_github()` syncs them to the existing repository.  
+//! The settings that are not in the table keep the GitHub value.  
+//! The new repository gets its first push on the `default_branch`, otherwise on the default branch of the remote.  
+//! Without `homepage` in the table, the new and the existing repository get the `homepage` or `documentation` of the package.  
+//! The sync also adds the `categories` to the topics and prints every changed field.
+//!
 //! ## GitHub API secret_token
 //!
 //! The GitHub API secret_token is a secret just like a password. Maybe even greater.  
@@ -104,6 +168,22 @@
 //! But the true problem arises at the moment when you want to use the secret_token. How to trust the code you are giving the secret_token to?  
 //! Probably the best is that this code is written by you or that you have complete control over it. This makes very cumbersome the use of libraries/crates. You cannot trust them by default. However, it is impossible to avoid trust in low-level crates/libraries.
 //!
+//! The library hands the description of every request it sends to `authorize_github_request()`, but this hook is only advisory.  
+//! The check that cannot be bypassed belongs inside your own `send_to_github_api()`, where every request must pass before it gets the secret_token:
+//!
+//! ```rust ignore
+//! fn send_to_github_api(&self, req: reqwest::blocking::RequestBuilder) -> serde_json::Value {
+//!     let github_request = cgl::GitHubRequest::from_request_builder(&req).unwrap();
+//!     cgl::GitHubRequestAllowlist::new(&["get_release_by_tag", "create_release", "upload_release_asset"])
+//!         .check(&github_request)
+//!         .unwrap();
+//!     let req = req.bearer_auth(self.decrypt_secret_token_in_memory().expose_secret());
+//!     ...
+//! }
+//! ```
+//!
+//! The upload has a stream body that cannot be cloned, so `upload_to_github()` describes it with `cgl::GitHubRequest::split_async_request_builder(req)`.
+//!
 //! ## Open-source and free as a beer
 //!
 //! My open-source projects are free as a beer (MIT license).  
@@ -120,22 +200,325 @@
 //!
 // endregion: auto_md_to_doc_comments include README.md A //!
 
+// the blocking functions are thin wrappers around the async functions,
+// without the feature `async` the unused async functions are compiled but not public
+
 // region: mod, extern and use statements
+#[cfg(any(feature = "blocking", feature = "async"))]
+mod async_github_client_mod;
 mod auto_github_api_mod;
+mod dry_run_github_client_mod;
+mod error_mod;
+mod github_endpoint_mod;
+mod github_headers_mod;
+mod github_metadata_mod;
+mod github_release_asset_mod;
+mod github_release_mod;
+mod github_request_mod;
+mod github_response_mod;
+mod github_types_mod;
+mod http_client_mod;
+mod mock_github_client_mod;
+mod pagination_mod;
+mod prompter_mod;
+mod rate_limit_mod;
+mod retry_mod;
+#[cfg(feature = "upload")]
+mod upload_progress_mod;
 mod utils_mod;
 
 // reexport functions for callers of the library
 
+#[cfg(feature = "async")]
+pub use async_github_client_mod::SendToGitHubApiAsync;
+
+#[cfg(feature = "blocking")]
 pub use auto_github_api_mod::description_and_topics_to_github;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::description_and_topics_to_github_async;
 pub use auto_github_api_mod::git_has_remote;
 pub use auto_github_api_mod::git_has_upstream;
+#[cfg(feature = "blocking")]
 pub use auto_github_api_mod::github_api_create_a_github_pages_site;
-pub use auto_github_api_mod::github_api_create_new_release;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_create_a_github_pages_site_async;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::github_api_create_a_github_pages_site_with_source;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_create_a_github_pages_site_with_source_async;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::github_api_create_repository_from_template;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_create_repository_from_template_async;
+#[cfg(feature = "blocking")]
 pub use auto_github_api_mod::github_api_get_authenticated_user;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_get_authenticated_user_async;
+#[cfg(feature = "blocking")]
 pub use auto_github_api_mod::github_api_get_organization;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_get_organization_async;
+#[cfg(feature = "blocking")]
 pub use auto_github_api_mod::github_api_get_repository;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_get_repository_async;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::github_api_organization_repository_new;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_organization_repository_new_async;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::github_api_organization_repository_new_with_options;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_organization_repository_new_with_options_async;
+#[cfg(feature = "blocking")]
 pub use auto_github_api_mod::github_api_replace_all_topics;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_replace_all_topics_async;
+#[cfg(feature = "blocking")]
 pub use auto_github_api_mod::github_api_update_description;
-pub use auto_github_api_mod::github_api_upload_asset_to_release;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_update_description_async;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::github_api_update_repository;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_update_repository_async;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::github_api_user_repository_new;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_user_repository_new_async;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::github_api_user_repository_new_with_options;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::github_api_user_repository_new_with_options_async;
+#[cfg(all(feature = "blocking", feature = "interactive"))]
 pub use auto_github_api_mod::new_remote_github_repository;
+#[cfg(feature = "async")]
+pub use auto_github_api_mod::new_remote_github_repository_async;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::try_description_and_topics_to_github;
+pub use auto_github_api_mod::try_git_has_remote;
+pub use auto_github_api_mod::try_git_has_upstream;
+#[cfg(all(feature = "blocking", feature = "interactive"))]
+pub use auto_github_api_mod::try_new_remote_github_repository;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::try_new_remote_github_repository_with_options;
+#[cfg(feature = "blocking")]
+pub use auto_github_api_mod::try_new_remote_github_repository_with_prompter;
+pub use auto_github_api_mod::NewRepositoryOptions;
+pub use auto_github_api_mod::RepositoryTemplate;
+pub use auto_github_api_mod::RepositoryVisibility;
+#[cfg(feature = "blocking")]
 pub use auto_github_api_mod::SendToGitHubApi;
+pub use auto_github_api_mod::UpdateRepository;
+
+pub use dry_run_github_client_mod::DryRunGitHubClient;
+
+pub use error_mod::check_github_api_response;
+pub use error_mod::GitHubLibError;
+pub use error_mod::ResultWithGitHubLibError;
+
+pub use github_endpoint_mod::github_endpoint;
+pub use github_endpoint_mod::set_github_endpoint;
+pub use github_endpoint_mod::GitHubEndpoint;
+
+pub use github_headers_mod::github_headers;
+pub use github_headers_mod::set_github_headers;
+pub use github_headers_mod::GitHubHeaders;
+
+pub use github_metadata_mod::cargo_toml_github_metadata;
+pub use github_metadata_mod::GitHubMetadata;
+pub use github_metadata_mod::PagesSource;
+pub use github_metadata_mod::RepositoryMetadataChange;
+
+#[cfg(feature = "blocking")]
+pub use github_release_asset_mod::delete_release_asset;
+#[cfg(feature = "async")]
+pub use github_release_asset_mod::delete_release_asset_async;
+#[cfg(feature = "blocking")]
+pub use github_release_asset_mod::get_release_asset;
+#[cfg(feature = "async")]
+pub use github_release_asset_mod::get_release_asset_async;
+#[cfg(feature = "blocking")]
+pub use github_release_asset_mod::github_api_delete_release_asset;
+#[cfg(feature = "async")]
+pub use github_release_asset_mod::github_api_delete_release_asset_async;
+#[cfg(feature = "blocking")]
+pub use github_release_asset_mod::github_api_get_release_asset;
+#[cfg(feature = "async")]
+pub use github_release_asset_mod::github_api_get_release_asset_async;
+#[cfg(feature = "blocking")]
+pub use github_release_asset_mod::github_api_list_release_assets;
+#[cfg(feature = "async")]
+pub use github_release_asset_mod::github_api_list_release_assets_async;
+#[cfg(feature = "blocking")]
+pub use github_release_asset_mod::github_api_update_release_asset;
+#[cfg(feature = "async")]
+pub use github_release_asset_mod::github_api_update_release_asset_async;
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub use github_release_asset_mod::github_api_upload_asset_to_release;
+#[cfg(feature = "blocking")]
+pub use github_release_asset_mod::list_release_assets;
+#[cfg(feature = "async")]
+pub use github_release_asset_mod::list_release_assets_async;
+#[cfg(feature = "blocking")]
+pub use github_release_asset_mod::rename_release_asset;
+#[cfg(feature = "async")]
+pub use github_release_asset_mod::rename_release_asset_async;
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub use github_release_asset_mod::try_github_api_upload_asset_to_release;
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub use github_release_asset_mod::try_github_api_upload_asset_to_release_with_progress;
+#[cfg(feature = "upload")]
+pub use github_release_asset_mod::upload_asset_to_release_async;
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub use github_release_asset_mod::upload_assets_to_release;
+#[cfg(feature = "upload")]
+pub use github_release_asset_mod::upload_assets_to_release_async;
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub use github_release_asset_mod::upload_or_replace_asset;
+#[cfg(feature = "upload")]
+pub use github_release_asset_mod::upload_or_replace_asset_async;
+#[cfg(feature = "upload")]
+pub use github_release_asset_mod::AssetUploadResult;
+
+#[cfg(feature = "blocking")]
+pub use github_release_mod::create_or_update_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::create_or_update_release_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::delete_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::delete_release_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::get_latest_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::get_latest_release_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::get_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::get_release_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::get_release_by_tag;
+#[cfg(feature = "async")]
+pub use github_release_mod::get_release_by_tag_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::github_api_create_new_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::github_api_create_new_release_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::github_api_create_new_release_with_options;
+#[cfg(feature = "async")]
+pub use github_release_mod::github_api_create_new_release_with_options_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::github_api_delete_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::github_api_delete_release_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::github_api_get_latest_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::github_api_get_latest_release_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::github_api_get_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::github_api_get_release_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::github_api_get_release_by_tag;
+#[cfg(feature = "async")]
+pub use github_release_mod::github_api_get_release_by_tag_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::github_api_list_releases;
+#[cfg(feature = "async")]
+pub use github_release_mod::github_api_list_releases_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::github_api_update_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::github_api_update_release_async;
+pub use github_release_mod::is_semver_prerelease;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::list_releases;
+#[cfg(feature = "async")]
+pub use github_release_mod::list_releases_async;
+#[cfg(feature = "blocking")]
+pub use github_release_mod::update_release;
+#[cfg(feature = "async")]
+pub use github_release_mod::update_release_async;
+pub use github_release_mod::MakeLatest;
+pub use github_release_mod::NewReleaseOptions;
+pub use github_release_mod::UpdateRelease;
+
+pub use github_request_mod::GitHubRequest;
+pub use github_request_mod::GitHubRequestAllowlist;
+
+pub use github_response_mod::GitHubResponse;
+
+pub use github_types_mod::github_api_response_to;
+pub use github_types_mod::github_response_to;
+#[cfg(feature = "blocking")]
+pub use github_types_mod::send_to_github_api_as;
+#[cfg(feature = "async")]
+pub use github_types_mod::send_to_github_api_as_async;
+pub use github_types_mod::Organization;
+pub use github_types_mod::Release;
+pub use github_types_mod::ReleaseAsset;
+pub use github_types_mod::Repository;
+pub use github_types_mod::Topics;
+pub use github_types_mod::User;
+
+pub use http_client_mod::async_http_client;
+#[cfg(feature = "blocking")]
+pub use http_client_mod::http_client;
+pub use http_client_mod::set_async_http_client;
+#[cfg(feature = "blocking")]
+pub use http_client_mod::set_http_client;
+pub use http_client_mod::set_http_client_config;
+pub use http_client_mod::HttpClientConfig;
+
+pub use mock_github_client_mod::MockGitHubClient;
+pub use mock_github_client_mod::RecordedRequest;
+
+pub use pagination_mod::next_page_url_from_link_header;
+#[cfg(feature = "blocking")]
+pub use pagination_mod::paginate;
+#[cfg(feature = "async")]
+pub use pagination_mod::paginate_async;
+#[cfg(feature = "blocking")]
+pub use pagination_mod::Paginator;
+
+pub use prompter_mod::FixedAnswerPrompter;
+#[cfg(feature = "interactive")]
+pub use prompter_mod::InquirePrompter;
+pub use prompter_mod::Prompter;
+pub use prompter_mod::ScriptedPrompter;
+
+#[cfg(feature = "blocking")]
+pub use rate_limit_mod::get_rate_limit;
+#[cfg(feature = "async")]
+pub use rate_limit_mod::get_rate_limit_async;
+#[cfg(feature = "blocking")]
+pub use rate_limit_mod::github_api_get_rate_limit;
+#[cfg(feature = "async")]
+pub use rate_limit_mod::github_api_get_rate_limit_async;
+pub use rate_limit_mod::is_rate_limit_json;
+pub use rate_limit_mod::is_rate_limit_response;
+pub use rate_limit_mod::retry_after_from_headers;
+#[cfg(feature = "blocking")]
+pub use rate_limit_mod::wait_if_rate_limit_low;
+#[cfg(feature = "async")]
+pub use rate_limit_mod::wait_if_rate_limit_low_async;
+pub use rate_limit_mod::RateLimit;
+pub use rate_limit_mod::RateLimitOverview;
+
+pub use retry_mod::retry_policy;
+#[cfg(feature = "blocking")]
+pub use retry_mod::send_to_github_api_with_retry;
+#[cfg(feature = "async")]
+pub use retry_mod::send_to_github_api_with_retry_async;
+pub use retry_mod::set_retry_policy;
+pub use retry_mod::RetryPolicy;
+
+#[cfg(feature = "upload")]
+pub use upload_progress_mod::default_upload_progress;
+#[cfg(feature = "upload")]
+pub use upload_progress_mod::TerminalProgressBar;
+#[cfg(feature = "upload")]
+pub use upload_progress_mod::UploadProgress;
@@ -0,0 +1,172 @@
+// mock_github_client_mod.rs
+
+//! In-memory mock of the GitHubClient for unit testing without secret_token and network.
+//!
+//! The MockGitHubClient records every request it receives and
+//! replies with the scripted responses in the same order as they were pushed.
+//! A request without a scripted response panics, so an unexpected request fails the test.
+//! The scripted json gets the status guessed by GitHubResponse::from_json(),
+//! the scripted GitHubResponse has the exact status and headers.
+//! It implements both SendToGitHubApi and SendToGitHubApiAsync.
+//! Then the test can assert the exact calls made by the library.
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+use crate::github_response_mod::GitHubResponse;
+
+/// One request recorded by the MockGitHubClient
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// GET, POST, PATCH, PUT or DELETE
+    pub method: reqwest::Method,
+    /// the full url with the query
+    pub url: String,
+    /// header names and values in the order they were added
+    pub headers: Vec<(String, String)>,
+    /// the json body, None if there is no body or it is not json (like the stream for uploads)
+    pub body: Option<serde_json::Value>,
+}
+
+impl RecordedRequest {
+    /// Record the data from a built request
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    pub(crate) fn from_request(request: &reqwest::Request) -> Self {
+        Self::new(
+            request.method(),
+            request.url(),
+            request.headers(),
+            request.body().and_then(|body| body.as_bytes()),
+        )
+    }
+
+    /// Record the data from a built blocking request
+    #[cfg(feature = "blocking")]
+    pub(crate) fn from_blocking_request(request: &reqwest::blocking::Request) -> Self {
+        Self::new(
+            request.method(),
+            request.url(),
+            request.headers(),
+            request.body().and_then(|body| body.as_bytes()),
+        )
+    }
+
+    /// Record the data common to both kinds of requests
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn new(method: &reqwest::Method, url: &url::Url, headers: &reqwest::header::HeaderMap, body: Option<&[u8]>) -> Self {
+        RecordedRequest {
+            method: method.clone(),
+            url: url.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_str().unwrap_or_default().to_string()))
+                .collect(),
+            body: body.and_then(|body| serde_json::from_slice(body).ok()),
+        }
+    }
+
+    /// Get the value of a header by name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Mock GitHubClient with recorded requests and scripted responses
+#[derive(Debug, Default)]
+pub struct MockGitHubClient {
+    recorded_requests: std::sync::Mutex<Vec<RecordedRequest>>,
+    responses: std::sync::Mutex<std::collections::VecDeque<GitHubResponse>>,
+}
+
+impl MockGitHubClient {
+    /// New mock client without scripted responses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New mock client with scripted responses in the order of the expected requests
+    pub fn with_responses(responses: Vec<serde_json::Value>) -> Self {
+        let mock_github_client = Self::default();
+        for response in responses {
+            mock_github_client.push_response(response);
+        }
+        mock_github_client
+    }
+
+    /// Add a scripted json response for the next request
+    pub fn push_response(&self, response: serde_json::Value) {
+        self.push_github_response(GitHubResponse::from_json(response));
+    }
+
+    /// Add a scripted response with status and headers for the next request
+    pub fn push_github_response(&self, github_response: GitHubResponse) {
+        self.responses.lock().unwrap_or_else(|e| e.into_inner()).push_back(github_response);
+    }
+
+    /// All the requests received until now
+    pub fn recorded_requests(&self) -> Vec<RecordedRequest> {
+        self.recorded_requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Record the request and return the next scripted response
+    ///
+    /// When there is no scripted response left, the request was not expected and the test panics.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn record_and_respond(&self, recorded_request: RecordedRequest) -> GitHubResponse {
+        let unexpected_request = format!("{} {}", recorded_request.method, recorded_request.url);
+        self.recorded_requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(recorded_request);
+        self.responses
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop_front()
+            .unwrap_or_else(|| panic!("MockGitHubClient has no scripted response for the unexpected request: {unexpected_request}"))
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl SendToGitHubApi for MockGitHubClient {
+    /// Record the request and return the next scripted response
+    fn send_to_github_api(&self, req: reqwest::blocking::RequestBuilder) -> serde_json::Value {
+        self.send_to_github_api_with_response(req).body
+    }
+
+    /// Record the request and return the next scripted response
+    fn send_to_github_api_with_response(&self, req: reqwest::blocking::RequestBuilder) -> GitHubResponse {
+        let request = req
+            .build()
+            .unwrap_or_else(|e| panic!("MockGitHubClient cannot build the request: {e}"));
+        self.record_and_respond(RecordedRequest::from_blocking_request(&request))
+    }
+
+    /// Record the request and return the next scripted response
+    ///
+    /// The body of the upload is a stream and it is not recorded.
+    fn upload_to_github(&self, req: reqwest::RequestBuilder) -> impl std::future::Future<Output = serde_json::Value> + Send {
+        let request = req
+            .build()
+            .unwrap_or_else(|e| panic!("MockGitHubClient cannot build the request: {e}"));
+        let response = self.record_and_respond(RecordedRequest::from_request(&request)).body;
+        async move { response }
+    }
+}
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+impl SendToGitHubApiAsync for MockGitHubClient {
+    /// Record the request and return the next scripted response
+    ///
+    /// The body of the upload is a stream and it is not recorded.
+    fn send_to_github_api_async(&self, req: reqwest::RequestBuilder) -> impl std::future::Future<Output = GitHubResponse> + Send {
+        let request = req
+            .build()
+            .unwrap_or_else(|e| panic!("MockGitHubClient cannot build the request: {e}"));
+        let response = self.record_and_respond(RecordedRequest::from_request(&request));
+        async move { response }
+    }
+}
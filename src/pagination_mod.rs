@@ -0,0 +1,212 @@
+// pagination_mod.rs
+
+//! Pagination of list endpoints.
+//!
+//! GitHub API returns lists page after page, 30 items by default and maximum 100 items per page.
+//! The Paginator calls the request factory for every page and yields all the items one by one.
+//! When the response has the `Link` header, it follows the url with `rel="next"`.
+//! Without the header, it stops after a page with less items than per_page.
+//! ```rust ignore
+//! let releases: Vec<cgl::Release> = cgl::paginate(&github_client, 100, |per_page, page| {
+//!     cgl::github_api_list_releases(&owner, &repo, per_page, page)
+//! })
+//! .collect::<Result<_, _>>()?;
+//! ```
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::github_api_request_builder;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::auto_github_api_mod::github_api_request_builder_async;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::error_mod::GitHubLibError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::error_mod::ResultWithGitHubLibError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::async_request_operation;
+#[cfg(feature = "blocking")]
+use crate::github_types_mod::request_operation;
+#[cfg(feature = "blocking")]
+use crate::retry_mod::send_to_github_api_with_retry;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::retry_mod::send_to_github_api_with_retry_async;
+
+/// Maximum number of items in one page of a list from GitHub API
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) const MAX_PER_PAGE: u32 = 100;
+
+/// Iterator over all the items of a list endpoint, page after page
+///
+/// The request for the next page is sent only when the items of the previous page are used up.
+/// After an error the iterator stops.
+#[cfg(feature = "blocking")]
+pub struct Paginator<'a, C, T, F>
+where
+    C: SendToGitHubApi,
+    T: serde::de::DeserializeOwned,
+    F: FnMut(u32, u32) -> reqwest::blocking::RequestBuilder,
+{
+    github_client: &'a C,
+    /// creates the request for per_page and page
+    request_for_page: F,
+    per_page: u32,
+    /// None after the last page
+    next_page: Option<NextPage>,
+    items: std::collections::VecDeque<T>,
+}
+
+/// How to request the next page
+#[cfg(any(feature = "blocking", feature = "async"))]
+enum NextPage {
+    /// page number for the request factory
+    Page(u32),
+    /// url from the `Link` header
+    Url(String),
+}
+
+#[cfg(feature = "blocking")]
+impl<'a, C, T, F> Paginator<'a, C, T, F>
+where
+    C: SendToGitHubApi,
+    T: serde::de::DeserializeOwned,
+    F: FnMut(u32, u32) -> reqwest::blocking::RequestBuilder,
+{
+    /// Send the request for the next page and keep its items
+    fn fetch_next_page(&mut self) -> ResultWithGitHubLibError<()> {
+        let Some(next_page) = self.next_page.take() else {
+            return Ok(());
+        };
+        let (page, req) = match next_page {
+            NextPage::Page(page) => (page, (self.request_for_page)(self.per_page, page)),
+            NextPage::Url(url) => (0, github_api_request_builder(reqwest::Method::GET, &url)),
+        };
+        let operation = request_operation(&req);
+        let github_response = send_to_github_api_with_retry(self.github_client, req)?;
+        let has_link_header = github_response.header("link").is_some();
+        let next_page_url = github_response.next_page_url();
+        let page_items = page_items_from_json(github_response.into_result(&operation)?)?;
+        if has_link_header {
+            self.next_page = next_page_url.map(NextPage::Url);
+        } else if page > 0 && page_items.len() >= self.per_page as usize {
+            self.next_page = Some(NextPage::Page(page + 1));
+        }
+        for item in page_items {
+            self.items.push_back(serde_json::from_value(item)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<C, T, F> Iterator for Paginator<'_, C, T, F>
+where
+    C: SendToGitHubApi,
+    T: serde::de::DeserializeOwned,
+    F: FnMut(u32, u32) -> reqwest::blocking::RequestBuilder,
+{
+    type Item = ResultWithGitHubLibError<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.items.is_empty() {
+            self.next_page.as_ref()?;
+            if let Err(error) = self.fetch_next_page() {
+                self.items.clear();
+                return Some(Err(error));
+            }
+        }
+        self.items.pop_front().map(Ok)
+    }
+}
+
+/// Iterate over all the items of a list endpoint, page after page
+///
+/// The request_for_page factory gets per_page and page, like the github_api_list_* functions.
+/// The items are typed structs or simply `serde_json::Value`.
+/// The per_page is limited to the GitHub maximum of 100.
+#[cfg(feature = "blocking")]
+pub fn paginate<C, T, F>(github_client: &C, per_page: u32, request_for_page: F) -> Paginator<'_, C, T, F>
+where
+    C: SendToGitHubApi,
+    T: serde::de::DeserializeOwned,
+    F: FnMut(u32, u32) -> reqwest::blocking::RequestBuilder,
+{
+    Paginator {
+        github_client,
+        request_for_page,
+        per_page: per_page.clamp(1, MAX_PER_PAGE),
+        next_page: Some(NextPage::Page(1)),
+        items: std::collections::VecDeque::new(),
+    }
+}
+
+/// Get all the items of a list endpoint, page after page, async
+///
+/// Look at paginate() for details. It returns all the items at once.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn paginate_async<T, F>(
+    github_client: &impl SendToGitHubApiAsync,
+    per_page: u32,
+    mut request_for_page: F,
+) -> ResultWithGitHubLibError<Vec<T>>
+where
+    T: serde::de::DeserializeOwned,
+    F: FnMut(u32, u32) -> reqwest::RequestBuilder,
+{
+    let per_page = per_page.clamp(1, MAX_PER_PAGE);
+    let mut items = vec![];
+    let mut next_page = Some(NextPage::Page(1));
+    while let Some(this_page) = next_page.take() {
+        let (page, req) = match this_page {
+            NextPage::Page(page) => (page, request_for_page(per_page, page)),
+            NextPage::Url(url) => (0, github_api_request_builder_async(reqwest::Method::GET, &url)),
+        };
+        let operation = async_request_operation(&req);
+        let github_response = send_to_github_api_with_retry_async(github_client, req).await?;
+        let has_link_header = github_response.header("link").is_some();
+        let next_page_url = github_response.next_page_url();
+        let page_items = page_items_from_json(github_response.into_result(&operation)?)?;
+        if has_link_header {
+            next_page = next_page_url.map(NextPage::Url);
+        } else if page > 0 && page_items.len() >= per_page as usize {
+            next_page = Some(NextPage::Page(page + 1));
+        }
+        for item in page_items {
+            items.push(serde_json::from_value(item)?);
+        }
+    }
+    Ok(items)
+}
+
+/// The items of one page
+///
+/// Most list endpoints return a json array.
+/// Some return an object with `total_count` and one array like `items`, `repositories` or `workflow_runs`.
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn page_items_from_json(json_value: serde_json::Value) -> ResultWithGitHubLibError<Vec<serde_json::Value>> {
+    let items = match json_value {
+        serde_json::Value::Array(items) => Some(items),
+        serde_json::Value::Object(map) => map.into_iter().find_map(|(_, value)| match value {
+            serde_json::Value::Array(items) => Some(items),
+            _ => None,
+        }),
+        _ => None,
+    };
+    items.ok_or_else(|| GitHubLibError::UnexpectedResponseError {
+        operation: "paginate".to_string(),
+        field: "array of items".to_string(),
+    })
+}
+
+/// Find the url of the next page in the `Link` response header
+///
+/// The header looks like `<https://api.github.com/...&page=2>; rel="next", <https://api.github.com/...&page=5>; rel="last"`.
+pub fn next_page_url_from_link_header(link_header: &str) -> Option<String> {
+    link_header.split(',').find_map(|link| {
+        let (url, params) = link.split_once(';')?;
+        let is_next = params.split(';').any(|param| param.trim().replace(' ', "") == "rel=\"next\"");
+        is_next.then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
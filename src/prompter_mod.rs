@@ -0,0 +1,103 @@
+// prompter_mod.rs
+
+//! Questions to the user go through the Prompter trait.
+//!
+//! In the terminal the InquirePrompter asks the user.
+//! In CI and scripted bootstrap the FixedAnswerPrompter answers without asking, so nothing hangs or panics.
+//! In tests the ScriptedPrompter answers in order and records the questions.
+//! ```rust ignore
+//! cgl::try_new_remote_github_repository_with_prompter(&github_client, &cgl::FixedAnswerPrompter::yes())?;
+//! ```
+
+#[cfg(feature = "interactive")]
+use cargo_auto_lib as cl;
+
+#[cfg(feature = "interactive")]
+use cl::BLUE;
+#[cfg(feature = "interactive")]
+use cl::RESET;
+
+use crate::error_mod::GitHubLibError;
+use crate::error_mod::ResultWithGitHubLibError;
+
+/// Every interactive decision in the library goes through this trait
+pub trait Prompter {
+    /// Ask the yes/no question and return true for yes
+    fn confirm(&self, question: &str) -> ResultWithGitHubLibError<bool>;
+}
+
+/// Ask the user in the terminal with inquire
+#[cfg(feature = "interactive")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InquirePrompter;
+
+#[cfg(feature = "interactive")]
+impl Prompter for InquirePrompter {
+    /// Only the answer `y` is yes
+    fn confirm(&self, question: &str) -> ResultWithGitHubLibError<bool> {
+        let answer = inquire::Text::new(&format!("{BLUE}{question} (y/n){RESET}"))
+            .prompt()
+            .map_err(|e| GitHubLibError::ErrorFromString(e.to_string()))?;
+        Ok(answer.to_lowercase() == "y")
+    }
+}
+
+/// Always the same answer without asking, for CI and scripted bootstrap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedAnswerPrompter {
+    pub answer: bool,
+}
+
+impl FixedAnswerPrompter {
+    /// Always answer yes
+    pub fn yes() -> Self {
+        FixedAnswerPrompter { answer: true }
+    }
+
+    /// Always answer no
+    pub fn no() -> Self {
+        FixedAnswerPrompter { answer: false }
+    }
+}
+
+impl Prompter for FixedAnswerPrompter {
+    fn confirm(&self, _question: &str) -> ResultWithGitHubLibError<bool> {
+        Ok(self.answer)
+    }
+}
+
+/// Scripted answers in the order of the expected questions, for tests
+#[derive(Debug, Default)]
+pub struct ScriptedPrompter {
+    answers: std::sync::Mutex<std::collections::VecDeque<bool>>,
+    questions: std::sync::Mutex<Vec<String>>,
+}
+
+impl ScriptedPrompter {
+    /// New prompter with the answers in order
+    pub fn new(answers: Vec<bool>) -> Self {
+        ScriptedPrompter {
+            answers: std::sync::Mutex::new(answers.into()),
+            questions: std::sync::Mutex::new(vec![]),
+        }
+    }
+
+    /// All the questions asked until now
+    pub fn questions(&self) -> Vec<String> {
+        self.questions.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl Prompter for ScriptedPrompter {
+    /// Record the question and return the next scripted answer
+    ///
+    /// When there is no scripted answer left, it returns an error.
+    fn confirm(&self, question: &str) -> ResultWithGitHubLibError<bool> {
+        self.questions.lock().unwrap_or_else(|e| e.into_inner()).push(question.to_string());
+        self.answers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop_front()
+            .ok_or_else(|| GitHubLibError::ErrorFromString(format!("ScriptedPrompter has no answer for the question: {question}")))
+    }
+}
@@ -0,0 +1,229 @@
+// rate_limit_mod.rs
+
+//! Rate limits of GitHub API.
+//!
+//! GitHub limits the number of requests per hour (primary rate limit)
+//! and also the bursts of requests (secondary rate limit).
+//! The responses contain the `X-RateLimit-*` headers and the `/rate_limit` endpoint returns the current state.
+//! Calling the `/rate_limit` endpoint does not count against the primary rate limit.
+//! Bulk operations over many repositories can check the remaining requests before every repository
+//! or opt into waiting for the reset with RetryPolicy::wait_for_rate_limit().
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cargo_auto_lib as cl;
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cl::RESET;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cl::YELLOW;
+
+#[cfg(feature = "blocking")]
+use crate::async_github_client_mod::BlockingGitHubClient;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::error_mod::ResultWithGitHubLibError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_endpoint_mod::github_endpoint;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_request_mod::GitHubRequest;
+use crate::github_response_mod::GitHubResponse;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_types_mod::github_response_to;
+#[cfg(feature = "blocking")]
+use crate::utils_mod::block_on;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::utils_mod::sleep_async;
+
+/// GitHub recommends to wait at least one minute after the secondary rate limit without the Retry-After header
+#[cfg(any(feature = "blocking", feature = "async"))]
+const SECONDARY_RATE_LIMIT_WAIT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// State of one rate limit
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    /// maximum number of requests per hour
+    pub limit: u64,
+    /// number of requests remaining in the current window
+    pub remaining: u64,
+    /// the time when the window resets in UTC epoch seconds
+    pub reset: u64,
+    /// number of requests used in the current window
+    #[serde(default)]
+    pub used: u64,
+    /// core, search, graphql,...
+    #[serde(default)]
+    pub resource: Option<String>,
+}
+
+impl RateLimit {
+    /// Parse the rate limit from the `X-RateLimit-*` response headers
+    ///
+    /// Returns None if the mandatory headers are missing.
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<RateLimit> {
+        let header_u64 = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok();
+        Some(RateLimit {
+            limit: header_u64("x-ratelimit-limit")?,
+            remaining: header_u64("x-ratelimit-remaining")?,
+            reset: header_u64("x-ratelimit-reset")?,
+            used: header_u64("x-ratelimit-used").unwrap_or_default(),
+            resource: headers
+                .get("x-ratelimit-resource")
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string()),
+        })
+    }
+
+    /// The wait until the window resets, zero if it is already reset
+    pub fn duration_until_reset(&self) -> std::time::Duration {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        std::time::Duration::from_secs(self.reset.saturating_sub(now))
+    }
+}
+
+/// All the rate limits of the authenticated user from the `/rate_limit` endpoint
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct RateLimitOverview {
+    /// rate limits for core, search, graphql,... by name
+    #[serde(default)]
+    pub resources: std::collections::HashMap<String, RateLimit>,
+    /// the core rate limit used by the REST API
+    pub rate: RateLimit,
+}
+
+/// Parse the `Retry-After` response header in seconds
+///
+/// GitHub sends it with the secondary rate limit and with 429 and 503 responses.
+pub fn retry_after_from_headers(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+    let seconds = headers.get("retry-after")?.to_str().ok()?.trim().parse::<u64>().ok()?;
+    Some(std::time::Duration::from_secs(seconds))
+}
+
+/// Is this json response from GitHub a primary or secondary rate limit error
+pub fn is_rate_limit_json(json_value: &serde_json::Value) -> bool {
+    json_value
+        .get("message")
+        .and_then(|message| message.as_str())
+        .map(|message| message.to_lowercase())
+        .is_some_and(|message| message.contains("rate limit") || message.contains("abuse"))
+}
+
+/// Is this response from GitHub a primary or secondary rate limit error
+///
+/// The 403 or 429 with the `Retry-After` header or without remaining requests is the rate limit.
+pub fn is_rate_limit_response(github_response: &GitHubResponse) -> bool {
+    if is_rate_limit_json(&github_response.body) {
+        return true;
+    }
+    matches!(github_response.status, 403 | 429)
+        && (github_response.retry_after().is_some() || github_response.rate_limit().is_some_and(|rate_limit| rate_limit.remaining == 0))
+}
+
+/// GitHub API get rate limit status for the authenticated user
+#[cfg(feature = "blocking")]
+pub fn github_api_get_rate_limit() -> reqwest::blocking::RequestBuilder {
+    get_rate_limit_request().request_builder()
+}
+
+/// GitHub API get rate limit status for the authenticated user, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub fn github_api_get_rate_limit_async() -> reqwest::RequestBuilder {
+    get_rate_limit_request().async_request_builder()
+}
+
+/// Describe the request to get rate limit status
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn get_rate_limit_request() -> GitHubRequest {
+    /*
+    https://docs.github.com/en/rest/rate-limit/rate-limit?apiVersion=2022-11-28#get-rate-limit-status-for-the-authenticated-user
+
+    curl -L \
+    -H "Accept: application/vnd.github+json" \
+    -H "Authorization: Bearer <YOUR-TOKEN>" \
+    -H "X-GitHub-Api-Version: 2022-11-28" \
+    https://api.github.com/rate_limit
+    */
+    let api_base_url = github_endpoint().api_base_url;
+    let rate_limit_url = format!("{api_base_url}/rate_limit");
+    GitHubRequest::from_url(reqwest::Method::GET, &rate_limit_url, None)
+}
+
+/// Get the rate limits of the authenticated user
+///
+/// This request is not retried, because it is used inside the retry itself.
+#[cfg(feature = "blocking")]
+pub fn get_rate_limit(github_client: &impl SendToGitHubApi) -> ResultWithGitHubLibError<RateLimitOverview> {
+    block_on(get_rate_limit_async(&BlockingGitHubClient::new(github_client)))
+}
+
+/// Get the rate limits of the authenticated user, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn get_rate_limit_async(github_client: &impl SendToGitHubApiAsync) -> ResultWithGitHubLibError<RateLimitOverview> {
+    let (github_request, req) = GitHubRequest::split_async_request_builder(github_api_get_rate_limit_async())?;
+    github_client.authorize_github_request(&github_request)?;
+    let github_response = github_client.send_to_github_api_async(req).await;
+    github_response_to("GET /rate_limit", github_response)
+}
+
+/// Wait for the reset if the core rate limit has less than min_remaining requests
+///
+/// Bulk operations call this before every repository to avoid the rate limit error in the middle of the work.
+#[cfg(feature = "blocking")]
+pub fn wait_if_rate_limit_low(github_client: &impl SendToGitHubApi, min_remaining: u64) -> ResultWithGitHubLibError<()> {
+    block_on(wait_if_rate_limit_low_async(
+        &BlockingGitHubClient::new(github_client),
+        min_remaining,
+    ))
+}
+
+/// Wait for the reset if the core rate limit has less than min_remaining requests, async
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn wait_if_rate_limit_low_async(github_client: &impl SendToGitHubApiAsync, min_remaining: u64) -> ResultWithGitHubLibError<()> {
+    let rate_limit_overview = get_rate_limit_async(github_client).await?;
+    if rate_limit_overview.rate.remaining < min_remaining {
+        let wait = rate_limit_overview.rate.duration_until_reset();
+        println!(
+            "    {YELLOW}Only {} GitHub API requests remaining. Waiting {} seconds for the reset.{RESET}",
+            rate_limit_overview.rate.remaining,
+            wait.as_secs()
+        );
+        sleep_async(wait).await;
+    }
+    Ok(())
+}
+
+/// The wait after a rate limit error, async
+///
+/// The `Retry-After` header has the priority, then the reset from the `X-RateLimit-Reset` header.
+/// Without headers, the secondary rate limit waits one minute
+/// and the primary rate limit waits until the reset from the `/rate_limit` endpoint plus one second.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) async fn rate_limit_wait_async(
+    github_client: &impl SendToGitHubApiAsync,
+    github_response: &GitHubResponse,
+) -> std::time::Duration {
+    if let Some(retry_after) = github_response.retry_after() {
+        return retry_after;
+    }
+    if let Some(rate_limit) = github_response.rate_limit().filter(|rate_limit| rate_limit.remaining == 0) {
+        return rate_limit.duration_until_reset() + std::time::Duration::from_secs(1);
+    }
+    let message = github_response
+        .body
+        .get("message")
+        .and_then(|message| message.as_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    if message.contains("secondary rate limit") || message.contains("abuse") {
+        return SECONDARY_RATE_LIMIT_WAIT;
+    }
+    match get_rate_limit_async(github_client).await {
+        Ok(rate_limit_overview) => rate_limit_overview.rate.duration_until_reset() + std::time::Duration::from_secs(1),
+        Err(_) => SECONDARY_RATE_LIMIT_WAIT,
+    }
+}
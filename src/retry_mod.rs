@@ -0,0 +1,285 @@
+// retry_mod.rs
+
+//! Retry with exponential backoff for transient failures of GitHub API.
+//!
+//! Network hiccups and GitHub server errors like 502 or 503 should not abort the whole release.
+//! The retry policy is stored for the whole library and by default there is no retry, like it always was.
+//! Set it once at the start of the automation task with set_retry_policy().
+//! All the functions in this library send the requests through send_to_github_api_with_retry() or send_to_github_api_with_retry_async().
+//! Waiting for the reset of the rate limit is opt-in with RetryPolicy::wait_for_rate_limit().
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cargo_auto_lib as cl;
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cl::RESET;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use cl::YELLOW;
+
+#[cfg(feature = "blocking")]
+use crate::async_github_client_mod::BlockingGitHubClient;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::async_github_client_mod::SendToGitHubApiAsync;
+#[cfg(feature = "blocking")]
+use crate::auto_github_api_mod::SendToGitHubApi;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::error_mod::ResultWithGitHubLibError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::github_request_mod::GitHubRequest;
+use crate::github_response_mod::GitHubResponse;
+use crate::rate_limit_mod::is_rate_limit_response;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::rate_limit_mod::rate_limit_wait_async;
+#[cfg(feature = "blocking")]
+use crate::utils_mod::block_on;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::utils_mod::sleep_async;
+
+/// How many times and how often to retry a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// 1 means no retry
+    pub max_attempts: u32,
+    /// the wait before the second attempt, then it doubles every attempt
+    pub initial_backoff: std::time::Duration,
+    /// the wait never gets longer than this
+    pub max_backoff: std::time::Duration,
+    /// HTTP status codes worth to retry. The 403 is retried only for the rate limit or abuse messages.
+    pub retryable_status_codes: Vec<u16>,
+    /// wait for the reset of the rate limit and then send the request once more
+    pub wait_for_rate_limit: bool,
+    /// do not wait for the rate limit reset longer than this, return the error instead
+    pub max_rate_limit_wait: std::time::Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::no_retry()
+    }
+}
+
+impl RetryPolicy {
+    /// Send the request only once
+    pub fn no_retry() -> Self {
+        Self::new(1)
+    }
+
+    /// Retry server errors and rate limits with the backoff from 1 second up to 60 seconds
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: std::time::Duration::from_secs(1),
+            max_backoff: std::time::Duration::from_secs(60),
+            retryable_status_codes: vec![403, 429, 500, 502, 503, 504],
+            wait_for_rate_limit: false,
+            max_rate_limit_wait: std::time::Duration::from_secs(3600),
+        }
+    }
+
+    /// The wait before the second attempt
+    pub fn initial_backoff(mut self, initial_backoff: std::time::Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// The maximum wait between attempts
+    pub fn max_backoff(mut self, max_backoff: std::time::Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// HTTP status codes worth to retry
+    pub fn retryable_status_codes(mut self, retryable_status_codes: &[u16]) -> Self {
+        self.retryable_status_codes = retryable_status_codes.to_vec();
+        self
+    }
+
+    /// Wait for the reset of the rate limit instead of returning the rate limit error
+    pub fn wait_for_rate_limit(mut self, max_rate_limit_wait: std::time::Duration) -> Self {
+        self.wait_for_rate_limit = true;
+        self.max_rate_limit_wait = max_rate_limit_wait;
+        self
+    }
+
+    /// The wait after the failed attempt number 1, 2, 3,...
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let multiplier = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(multiplier).min(self.max_backoff)
+    }
+
+    /// Is this status and message worth to retry
+    ///
+    /// The status 403 is also for missing permissions. It is retried only for the rate limit and abuse messages.
+    pub fn is_retryable(&self, status: u16, message: &str) -> bool {
+        if !self.retryable_status_codes.contains(&status) {
+            return false;
+        }
+        if status == 403 {
+            let message = message.to_lowercase();
+            return message.contains("rate limit") || message.contains("abuse");
+        }
+        true
+    }
+
+    /// Is this json response from GitHub worth to retry
+    ///
+    /// The GitHub error json contains the `message` and newer responses also the `status` as a string.
+    /// Without the status, the server error messages are recognized by text.
+    pub fn is_retryable_json(&self, json_value: &serde_json::Value) -> bool {
+        let Some(message) = json_value.get("message").and_then(|m| m.as_str()) else {
+            return false;
+        };
+        let status = json_value
+            .get("status")
+            .and_then(|s| s.as_str())
+            .and_then(|s| s.parse::<u16>().ok())
+            .or_else(|| status_from_message(message));
+        match status {
+            Some(status) => self.is_retryable(status, message),
+            None => false,
+        }
+    }
+
+    /// Is this response from GitHub worth to retry
+    ///
+    /// The 403 with the `Retry-After` header or without remaining requests is the rate limit.
+    /// The response from the json adapter has no headers, so the json is also checked.
+    pub fn is_retryable_response(&self, github_response: &GitHubResponse) -> bool {
+        let message = github_response.body.get("message").and_then(|m| m.as_str()).unwrap_or_default();
+        if is_rate_limit_response(github_response) {
+            // the json adapter guesses the status 400 for the rate limit message without the status
+            return self.retryable_status_codes.contains(&github_response.status) || self.is_retryable_json(&github_response.body);
+        }
+        self.is_retryable(github_response.status, message) || self.is_retryable_json(&github_response.body)
+    }
+}
+
+/// Guess the HTTP status from the GitHub error message
+fn status_from_message(message: &str) -> Option<u16> {
+    let message_lowercase = message.to_lowercase();
+    if message_lowercase.contains("rate limit") || message_lowercase.contains("abuse") {
+        Some(403)
+    } else if message_lowercase.contains("bad gateway") {
+        Some(502)
+    } else if message_lowercase.contains("service unavailable") {
+        Some(503)
+    } else if message_lowercase.contains("server error") {
+        Some(500)
+    } else {
+        None
+    }
+}
+
+/// The retry policy is stored for the whole library. None means no retry.
+static RETRY_POLICY: std::sync::RwLock<Option<RetryPolicy>> = std::sync::RwLock::new(None);
+
+/// Set the retry policy for all the requests from this library
+pub fn set_retry_policy(retry_policy: RetryPolicy) {
+    *RETRY_POLICY.write().unwrap_or_else(|e| e.into_inner()) = Some(retry_policy);
+}
+
+/// Get the retry policy used for all the requests from this library
+pub fn retry_policy() -> RetryPolicy {
+    RETRY_POLICY.read().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default()
+}
+
+/// Send the request with the GitHubClient and retry the transient failures
+///
+/// First the GitHubClient authorizes the description of the request, also the request with a stream body.
+/// The request is cloned for every attempt. The request with a stream body cannot be cloned and is sent only once.
+/// The `Retry-After` header overrides the backoff.
+/// With wait_for_rate_limit, the rate limit error waits once for the reset and does not count as an attempt.
+#[cfg(feature = "blocking")]
+pub fn send_to_github_api_with_retry(
+    github_client: &impl SendToGitHubApi,
+    req: reqwest::blocking::RequestBuilder,
+) -> ResultWithGitHubLibError<GitHubResponse> {
+    let (github_request, req) = GitHubRequest::split_request_builder(req)?;
+    github_client.authorize_github_request(&github_request)?;
+    let mut retry_state = RetryState::new();
+    loop {
+        let Some(req_for_attempt) = req.try_clone() else {
+            return Ok(github_client.send_to_github_api_with_response(req));
+        };
+        let github_response = github_client.send_to_github_api_with_response(req_for_attempt);
+        match block_on(retry_state.wait_before_retry_async(&BlockingGitHubClient::new(github_client), &github_response)) {
+            Some(wait) => std::thread::sleep(wait),
+            None => return Ok(github_response),
+        }
+    }
+}
+
+/// Send the async request with the GitHubClient and retry the transient failures
+///
+/// Look at send_to_github_api_with_retry() for details.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub async fn send_to_github_api_with_retry_async(
+    github_client: &impl SendToGitHubApiAsync,
+    req: reqwest::RequestBuilder,
+) -> ResultWithGitHubLibError<GitHubResponse> {
+    let (github_request, req) = GitHubRequest::split_async_request_builder(req)?;
+    github_client.authorize_github_request(&github_request)?;
+    let mut retry_state = RetryState::new();
+    loop {
+        let Some(req_for_attempt) = req.try_clone() else {
+            return Ok(github_client.send_to_github_api_async(req).await);
+        };
+        let github_response = github_client.send_to_github_api_async(req_for_attempt).await;
+        match retry_state.wait_before_retry_async(github_client, &github_response).await {
+            Some(wait) => sleep_async(wait).await,
+            None => return Ok(github_response),
+        }
+    }
+}
+
+/// The attempts of one request with the retry policy
+///
+/// Also the requests that cannot be cloned, like the upload stream, use it to retry with a new request.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) struct RetryState {
+    retry_policy: RetryPolicy,
+    attempt: u32,
+    waited_for_rate_limit: bool,
+}
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+impl RetryState {
+    /// The first attempt with the retry policy of the library
+    pub(crate) fn new() -> Self {
+        RetryState {
+            retry_policy: retry_policy(),
+            attempt: 1,
+            waited_for_rate_limit: false,
+        }
+    }
+
+    /// The wait before the next attempt or None if this response is the result
+    ///
+    /// The `Retry-After` header overrides the backoff.
+    /// With wait_for_rate_limit, the rate limit error waits once for the reset and does not count as an attempt.
+    pub(crate) async fn wait_before_retry_async(
+        &mut self,
+        github_client: &impl SendToGitHubApiAsync,
+        github_response: &GitHubResponse,
+    ) -> Option<std::time::Duration> {
+        if self.retry_policy.wait_for_rate_limit && !self.waited_for_rate_limit && is_rate_limit_response(github_response) {
+            let wait = rate_limit_wait_async(github_client, github_response).await;
+            if wait <= self.retry_policy.max_rate_limit_wait {
+                println!(
+                    "    {YELLOW}GitHub API rate limit exceeded. Waiting {} seconds.{RESET}",
+                    wait.as_secs()
+                );
+                self.waited_for_rate_limit = true;
+                return Some(wait);
+            }
+        }
+        if self.attempt >= self.retry_policy.max_attempts || !self.retry_policy.is_retryable_response(github_response) {
+            return None;
+        }
+        let wait = github_response
+            .retry_after()
+            .unwrap_or_else(|| self.retry_policy.backoff(self.attempt));
+        self.attempt += 1;
+        Some(wait)
+    }
+}
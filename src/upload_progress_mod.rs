@@ -0,0 +1,74 @@
+// upload_progress_mod.rs
+
+//! Progress of uploading big release assets.
+//!
+//! The upload stream calls the progress callback after every chunk of the file is read.
+//! The TerminalProgressBar is the default for interactive runs.
+
+use cargo_auto_lib as cl;
+
+use cl::RESET;
+use cl::YELLOW;
+
+/// Callback for the progress of the upload
+///
+/// Any closure `Fn(&str, u64, u64)` with file_name, bytes_sent and total_bytes is also an UploadProgress.
+pub trait UploadProgress: Send + Sync {
+    /// Called after every chunk of the file is sent to the upload stream
+    fn on_progress(&self, file_name: &str, bytes_sent: u64, total_bytes: u64);
+}
+
+impl<F> UploadProgress for F
+where
+    F: Fn(&str, u64, u64) + Send + Sync,
+{
+    fn on_progress(&self, file_name: &str, bytes_sent: u64, total_bytes: u64) {
+        self(file_name, bytes_sent, total_bytes)
+    }
+}
+
+/// Simple progress bar in the terminal
+///
+/// It writes to stderr only when the percent changes.
+/// Concurrent uploads share one line: every update overwrites it with its own file_name.
+#[derive(Debug, Default)]
+pub struct TerminalProgressBar {
+    /// the last printed percent for every file
+    last_percent: std::sync::Mutex<std::collections::HashMap<String, u64>>,
+}
+
+impl TerminalProgressBar {
+    /// Width of the bar in characters
+    const BAR_WIDTH: u64 = 40;
+
+    /// New progress bar
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl UploadProgress for TerminalProgressBar {
+    fn on_progress(&self, file_name: &str, bytes_sent: u64, total_bytes: u64) {
+        // an empty file is done immediately and a file that grew during the upload stays at 100%
+        let percent = (bytes_sent * 100).checked_div(total_bytes).unwrap_or(100).min(100);
+        let mut last_percent = self.last_percent.lock().unwrap_or_else(|e| e.into_inner());
+        if last_percent.get(file_name) == Some(&percent) {
+            return;
+        }
+        last_percent.insert(file_name.to_string(), percent);
+
+        let done = percent * Self::BAR_WIDTH / 100;
+        let bar = format!("{}{}", "#".repeat(done as usize), ".".repeat((Self::BAR_WIDTH - done) as usize));
+        let end_of_line = if percent == 100 { "\n" } else { "" };
+        eprint!("\r    {YELLOW}{file_name} [{bar}] {percent:>3}% {bytes_sent}/{total_bytes} bytes{RESET}{end_of_line}");
+    }
+}
+
+/// The default progress is the TerminalProgressBar for interactive runs and nothing for CI
+pub fn default_upload_progress() -> Option<std::sync::Arc<dyn UploadProgress>> {
+    if std::io::IsTerminal::is_terminal(&std::io::stderr()) {
+        Some(std::sync::Arc::new(TerminalProgressBar::new()))
+    } else {
+        None
+    }
+}
@@ -1,3 +1,55 @@
 // utils_mod.rs
 
 //! various utilities
+
+/// Waker that unparks the thread waiting in block_on()
+#[cfg(feature = "blocking")]
+struct ThreadWaker(std::thread::Thread);
+
+#[cfg(feature = "blocking")]
+impl std::task::Wake for ThreadWaker {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run the future to completion on the current thread
+///
+/// The blocking functions are thin wrappers around the async functions.
+/// This simple executor does not need a tokio runtime, so the blocking GitHubClient can use reqwest::blocking inside.
+#[cfg(feature = "blocking")]
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = std::task::Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = std::task::Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            std::task::Poll::Ready(output) => return output,
+            std::task::Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+/// Wait without blocking the tokio runtime if there is one, otherwise block the thread
+///
+/// Without the feature `async` the async functions run only inside the blocking wrappers, so the thread can sleep.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) async fn sleep_async(duration: std::time::Duration) {
+    #[cfg(feature = "async")]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        tokio::time::sleep(duration).await;
+        return;
+    }
+    std::thread::sleep(duration);
+}
+
+/// Run the future to completion on the current thread inside a new tokio runtime
+///
+/// The uploads need tokio for tokio::fs and for the async reqwest client.
+/// The runtime is only entered, not blocked on, so the blocking GitHubClient can still use reqwest::blocking inside.
+#[cfg(all(feature = "blocking", feature = "upload"))]
+pub(crate) fn block_on_in_tokio_runtime<F: std::future::Future>(future: F) -> std::io::Result<F::Output> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let _runtime_guard = runtime.enter();
+    Ok(block_on(future))
+}
@@ -0,0 +1,21 @@
+// github_endpoint_test.rs
+
+// The endpoint is stored for the whole library,
+// so this test is in its own file to run in a separate process.
+
+#[test]
+fn github_enterprise_server_endpoint() {
+    let github_endpoint = cargo_auto_github_lib::GitHubEndpoint::github_enterprise_server("ghe.corp");
+    assert_eq!(github_endpoint.api_base_url, "https://ghe.corp/api/v3");
+    assert_eq!(github_endpoint.uploads_base_url, "https://ghe.corp/api/uploads");
+    cargo_auto_github_lib::set_github_endpoint(github_endpoint);
+
+    let req = cargo_auto_github_lib::github_api_get_repository("bestia-dev", "hello_world")
+        .build()
+        .unwrap();
+    assert_eq!(req.url().as_str(), "https://ghe.corp/api/v3/repos/bestia-dev/hello_world");
+    assert!(cargo_auto_github_lib::github_endpoint().is_api_url(req.url()));
+
+    let evil_url = url::Url::parse("https://ghe.corp.evil.com/api/v3/user").unwrap();
+    assert!(!cargo_auto_github_lib::github_endpoint().is_api_url(&evil_url));
+}
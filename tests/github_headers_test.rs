@@ -0,0 +1,20 @@
+// github_headers_test.rs
+
+// The headers are stored for the whole library,
+// so this test is in its own file to run in a separate process.
+
+#[test]
+fn github_headers_with_mock() {
+    cargo_auto_github_lib::set_github_headers(
+        cargo_auto_github_lib::GitHubHeaders::new()
+            .user_agent("bestia_dev_release_tool/1.0")
+            .api_version("2026-03-10")
+            .extra_header("X-Team", "platform"),
+    );
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![serde_json::json!({"id": 1, "tag_name": "v1.0.0"})]);
+    cargo_auto_github_lib::get_release_by_tag(&github_client, "bestia-dev", "hello_world", "v1.0.0").unwrap();
+    let request = &github_client.recorded_requests()[0];
+    assert_eq!(request.header("User-Agent"), Some("bestia_dev_release_tool/1.0"));
+    assert_eq!(request.header("X-GitHub-Api-Version"), Some("2026-03-10"));
+    assert_eq!(request.header("X-Team"), Some("platform"));
+}
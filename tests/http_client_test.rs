@@ -0,0 +1,25 @@
+// http_client_test.rs
+
+// The HTTP clients are stored for the whole library,
+// so this test is in its own file to run in a separate process.
+
+#[test]
+fn http_client_config() {
+    let result = cargo_auto_github_lib::set_http_client_config(
+        &cargo_auto_github_lib::HttpClientConfig::new().root_certificate_pem(b"not a certificate"),
+    );
+    assert!(result.is_err());
+
+    cargo_auto_github_lib::set_http_client_config(
+        &cargo_auto_github_lib::HttpClientConfig::new()
+            .timeout(std::time::Duration::from_secs(60))
+            .connect_timeout(std::time::Duration::from_secs(10))
+            .proxy_url("http://proxy.example.com:8080"),
+    )
+    .unwrap();
+
+    // the builders use the shared client
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![serde_json::json!({"id": 1, "tag_name": "v1.0.0"})]);
+    let release = cargo_auto_github_lib::get_release_by_tag(&github_client, "bestia-dev", "hello_world", "v1.0.0").unwrap();
+    assert_eq!(release.unwrap().id, 1);
+}
@@ -58,3 +58,766 @@ fn get_id_from_json_1() {
 
     panic!("Just to show the dbg! in the terminal.")
 } */
+
+#[test]
+fn check_github_api_response_error() {
+    let json_value = serde_json::json!({
+        "message": "Validation Failed",
+        "errors": [
+            {"resource": "Release", "code": "already_exists", "field": "tag_name"},
+            {"message": "name already exists on this account"}
+        ],
+        "status": "422"
+    });
+    let result = cargo_auto_github_lib::check_github_api_response("create_new_release", json_value);
+    match result {
+        Err(cargo_auto_github_lib::GitHubLibError::GitHubApiError {
+            operation,
+            status,
+            message,
+            errors,
+        }) => {
+            assert_eq!(operation, "create_new_release");
+            assert_eq!(status, Some(422));
+            assert_eq!(message, "Validation Failed");
+            assert_eq!(
+                errors,
+                vec!["already_exists".to_string(), "name already exists on this account".to_string()]
+            );
+        }
+        _ => panic!("expected GitHubApiError"),
+    }
+
+    let json_value = serde_json::json!({"id": 1, "name": "v1.0.0"});
+    assert!(cargo_auto_github_lib::check_github_api_response("create_new_release", json_value).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "unexpected request: GET https://api.github.com/user")]
+fn mock_github_client_unexpected_request() {
+    let github_client = cargo_auto_github_lib::MockGitHubClient::new();
+    let _ = cargo_auto_github_lib::SendToGitHubApi::send_to_github_api(
+        &github_client,
+        cargo_auto_github_lib::github_api_get_authenticated_user(),
+    );
+}
+
+#[test]
+fn new_repository_options_body() {
+    // the default body is the same as before the options
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"id": 1, "name": "hello_world", "full_name": "bestia-dev/hello_world"}),
+        serde_json::json!({"id": 2, "name": "hello_world", "full_name": "automation-tasks-rs/hello_world"}),
+    ]);
+    cargo_auto_github_lib::SendToGitHubApi::send_to_github_api(
+        &github_client,
+        cargo_auto_github_lib::github_api_user_repository_new("bestia-dev", "hello_world", "Hello"),
+    );
+    let new_repository_options = cargo_auto_github_lib::NewRepositoryOptions::new()
+        .visibility(cargo_auto_github_lib::RepositoryVisibility::Internal)
+        .is_template(true)
+        .license_template("mit")
+        .allow_merge_commit(false)
+        .delete_branch_on_merge(true);
+    cargo_auto_github_lib::SendToGitHubApi::send_to_github_api(
+        &github_client,
+        cargo_auto_github_lib::github_api_organization_repository_new_with_options(
+            "automation-tasks-rs",
+            "hello_world",
+            "Hello",
+            &new_repository_options,
+        ),
+    );
+    let requests = github_client.recorded_requests();
+    let body = requests[0].body.clone().unwrap();
+    assert_eq!(body["private"], false);
+    assert_eq!(body["homepage"], "https://bestia-dev.github.io/hello_world");
+    assert_eq!(body["has_issues"], true);
+    assert_eq!(body["has_wiki"], false);
+    assert!(body.get("visibility").is_none());
+    assert!(body.get("allow_merge_commit").is_none());
+    let body = requests[1].body.clone().unwrap();
+    assert_eq!(requests[1].url, "https://api.github.com/orgs/automation-tasks-rs/repos");
+    assert_eq!(body["private"], true);
+    assert_eq!(body["visibility"], "internal");
+    assert_eq!(body["is_template"], true);
+    assert_eq!(body["license_template"], "mit");
+    assert_eq!(body["allow_merge_commit"], false);
+    assert_eq!(body["delete_branch_on_merge"], true);
+}
+
+#[test]
+fn release_lifecycle_with_mock() {
+    // the first page is full, so the second page must be requested
+    let full_page: Vec<serde_json::Value> = (1..=100)
+        .map(|id| serde_json::json!({"id": id, "tag_name": format!("v0.0.{id}")}))
+        .collect();
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::Value::Array(full_page),
+        serde_json::json!([{"id": 101, "tag_name": "v1.0.0", "draft": true}]),
+        serde_json::json!({"message": "Not Found", "status": "404"}),
+        serde_json::json!({"id": 101, "tag_name": "v1.0.0", "draft": false, "body": "fixed typo"}),
+        serde_json::json!({"id": 101, "tag_name": "v1.0.0", "draft": false, "body": "fixed typo"}),
+        serde_json::Value::Null,
+    ]);
+    let releases = cargo_auto_github_lib::list_releases(&github_client, "bestia-dev", "hello_world").unwrap();
+    assert_eq!(releases.len(), 101);
+    assert!(releases[100].draft);
+
+    let release = cargo_auto_github_lib::get_release_by_tag(&github_client, "bestia-dev", "hello_world", "v2.0.0").unwrap();
+    assert!(release.is_none());
+
+    let update_release = cargo_auto_github_lib::UpdateRelease::new().body("fixed typo").draft(false);
+    let release = cargo_auto_github_lib::update_release(&github_client, "bestia-dev", "hello_world", 101, &update_release).unwrap();
+    assert!(!release.draft);
+
+    let release = cargo_auto_github_lib::get_release(&github_client, "bestia-dev", "hello_world", 101).unwrap();
+    assert_eq!(release.body.as_deref(), Some("fixed typo"));
+
+    cargo_auto_github_lib::delete_release(&github_client, "bestia-dev", "hello_world", 101).unwrap();
+
+    let requests = github_client.recorded_requests();
+    assert_eq!(requests.len(), 6);
+    assert_eq!(
+        requests[0].url,
+        "https://api.github.com/repos/bestia-dev/hello_world/releases?per_page=100&page=1"
+    );
+    assert_eq!(
+        requests[1].url,
+        "https://api.github.com/repos/bestia-dev/hello_world/releases?per_page=100&page=2"
+    );
+    assert_eq!(
+        requests[2].url,
+        "https://api.github.com/repos/bestia-dev/hello_world/releases/tags/v2.0.0"
+    );
+    assert_eq!(requests[3].method, "PATCH");
+    assert_eq!(requests[3].body, Some(serde_json::json!({"body": "fixed typo", "draft": false})));
+    assert_eq!(requests[4].method, "GET");
+    assert_eq!(requests[4].url, "https://api.github.com/repos/bestia-dev/hello_world/releases/101");
+    assert_eq!(requests[5].method, "DELETE");
+    assert_eq!(requests[5].url, "https://api.github.com/repos/bestia-dev/hello_world/releases/101");
+}
+
+#[test]
+fn create_new_release_prerelease_from_version() {
+    assert!(cargo_auto_github_lib::is_semver_prerelease("v1.0.0-alpha.1"));
+    assert!(!cargo_auto_github_lib::is_semver_prerelease("1.0.0+build.5"));
+
+    let req = cargo_auto_github_lib::github_api_create_new_release(
+        "bestia-dev",
+        "hello_world",
+        "v1.0.0-rc.1",
+        "Version 1.0.0-rc.1",
+        "main",
+        "notes",
+    );
+    let request = req.build().unwrap();
+    let body: serde_json::Value = serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(body["prerelease"], true);
+    assert_eq!(body["draft"], false);
+    assert!(body.get("make_latest").is_none());
+
+    let new_release_options = cargo_auto_github_lib::NewReleaseOptions::new()
+        .draft(true)
+        .generate_release_notes(true)
+        .make_latest(cargo_auto_github_lib::MakeLatest::False)
+        .discussion_category_name("Announcements");
+    let req = cargo_auto_github_lib::github_api_create_new_release_with_options(
+        "bestia-dev",
+        "hello_world",
+        "v1.0.0",
+        "Version 1.0.0",
+        "main",
+        "notes",
+        &new_release_options,
+    );
+    let request = req.build().unwrap();
+    let body: serde_json::Value = serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(body["draft"], true);
+    assert_eq!(body["prerelease"], false);
+    assert_eq!(body["generate_release_notes"], true);
+    assert_eq!(body["make_latest"], "false");
+    assert_eq!(body["discussion_category_name"], "Announcements");
+}
+
+#[test]
+fn create_or_update_release_existing_draft_with_mock() {
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"message": "Not Found", "status": "404"}),
+        serde_json::json!([{"id": 7, "tag_name": "v1.0.0", "draft": true}]),
+        serde_json::json!({"id": 7, "tag_name": "v1.0.0", "draft": true, "name": "Version 1.0.0"}),
+    ]);
+    let release_id = cargo_auto_github_lib::create_or_update_release(
+        &github_client,
+        "bestia-dev",
+        "hello_world",
+        "v1.0.0",
+        "Version 1.0.0",
+        "main",
+        "notes",
+        &cargo_auto_github_lib::NewReleaseOptions::new(),
+    )
+    .unwrap();
+    assert_eq!(release_id, 7);
+
+    let requests = github_client.recorded_requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[2].method, "PATCH");
+    assert_eq!(requests[2].url, "https://api.github.com/repos/bestia-dev/hello_world/releases/7");
+    assert_eq!(
+        requests[2].body,
+        Some(serde_json::json!({"name": "Version 1.0.0", "body": "notes"}))
+    );
+}
+
+#[test]
+fn create_or_update_release_new_with_mock() {
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"message": "Not Found", "status": "404"}),
+        serde_json::json!([]),
+        serde_json::json!({"id": 8, "tag_name": "v1.0.1"}),
+    ]);
+    let release_id = cargo_auto_github_lib::create_or_update_release(
+        &github_client,
+        "bestia-dev",
+        "hello_world",
+        "v1.0.1",
+        "Version 1.0.1",
+        "main",
+        "notes",
+        &cargo_auto_github_lib::NewReleaseOptions::new(),
+    )
+    .unwrap();
+    assert_eq!(release_id, 8);
+
+    let requests = github_client.recorded_requests();
+    assert_eq!(requests[2].method, "POST");
+    assert_eq!(requests[2].url, "https://api.github.com/repos/bestia-dev/hello_world/releases");
+}
+
+/// The absolute path does not depend on the current directory of the parallel tests
+const UPLOAD_TEST_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/upload_test.txt");
+
+#[test]
+fn upload_or_replace_asset_with_mock() {
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!([{"id": 11, "name": "upload_test.txt"}, {"id": 12, "name": "other.tar.gz"}]),
+        serde_json::Value::Null,
+        serde_json::json!({"id": 13, "name": "upload_test.txt", "state": "uploaded"}),
+    ]);
+    let asset = cargo_auto_github_lib::upload_or_replace_asset(&github_client, "bestia-dev", "hello_world", 5, UPLOAD_TEST_FILE).unwrap();
+    assert_eq!(asset.id, 13);
+
+    let requests = github_client.recorded_requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[0].url,
+        "https://api.github.com/repos/bestia-dev/hello_world/releases/5/assets?per_page=100&page=1"
+    );
+    assert_eq!(requests[1].method, "DELETE");
+    assert_eq!(
+        requests[1].url,
+        "https://api.github.com/repos/bestia-dev/hello_world/releases/assets/11"
+    );
+    assert_eq!(requests[2].method, "POST");
+    assert_eq!(
+        requests[2].url,
+        "https://uploads.github.com/repos/bestia-dev/hello_world/releases/5/assets?name=upload_test.txt"
+    );
+    assert_eq!(requests[2].header("Content-Type"), Some("application/octet-stream"));
+}
+
+#[test]
+fn upload_assets_to_release_with_mock() {
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"id": 21, "name": "upload_test.txt"}),
+        serde_json::json!({"id": 22, "name": "upload_test.txt"}),
+    ]);
+    let report = cargo_auto_github_lib::upload_assets_to_release(
+        &github_client,
+        "bestia-dev",
+        "hello_world",
+        5,
+        &[
+            UPLOAD_TEST_FILE,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/does_not_exist.tar.gz"),
+            UPLOAD_TEST_FILE,
+        ],
+        2,
+        None,
+    )
+    .unwrap();
+    assert_eq!(report.len(), 3);
+    assert_eq!(report[0].path_to_file, UPLOAD_TEST_FILE);
+    assert!(report[0].result.is_ok());
+    assert!(matches!(
+        report[1].result,
+        Err(cargo_auto_github_lib::GitHubLibError::StdIoError(_))
+    ));
+    assert!(report[2].result.is_ok());
+    assert_eq!(github_client.recorded_requests().len(), 2);
+}
+
+/// Client that really sends the upload stream to a local server, so the stream calls the progress callback
+struct LocalServerGitHubClient {
+    local_address: std::net::SocketAddr,
+}
+
+impl cargo_auto_github_lib::SendToGitHubApiAsync for LocalServerGitHubClient {
+    fn send_to_github_api_async(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> impl std::future::Future<Output = cargo_auto_github_lib::GitHubResponse> + Send {
+        let (client, request) = req.build_split();
+        let mut request = request.unwrap();
+        *request.url_mut() = format!("http://{}/upload", self.local_address).parse().unwrap();
+        async move {
+            cargo_auto_github_lib::GitHubResponse::from_response(client.execute(request).await.unwrap())
+                .await
+                .unwrap()
+        }
+    }
+}
+
+/// Read the whole request and return the json of the new asset
+fn local_upload_server() -> std::net::SocketAddr {
+    use std::io::{BufRead, Read, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let local_address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = std::io::BufReader::new(stream);
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let json = r#"{"id": 31, "name": "upload_test.txt", "state": "uploaded"}"#;
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 201 Created\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{json}",
+            json.len()
+        )
+        .unwrap();
+    });
+    local_address
+}
+
+#[test]
+fn upload_progress_callback() {
+    let github_client = LocalServerGitHubClient {
+        local_address: local_upload_server(),
+    };
+    let progress = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let progress_for_callback = progress.clone();
+    let upload_progress = move |file_name: &str, bytes_sent: u64, total_bytes: u64| {
+        progress_for_callback
+            .lock()
+            .unwrap()
+            .push((file_name.to_string(), bytes_sent, total_bytes));
+    };
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let asset = runtime
+        .block_on(cargo_auto_github_lib::upload_asset_to_release_async(
+            &github_client,
+            "bestia-dev",
+            "hello_world",
+            5,
+            UPLOAD_TEST_FILE,
+            Some(std::sync::Arc::new(upload_progress)),
+        ))
+        .unwrap();
+    assert_eq!(asset.id, 31);
+
+    let file_size = std::fs::metadata(UPLOAD_TEST_FILE).unwrap().len();
+    let progress = progress.lock().unwrap();
+    assert!(!progress.is_empty());
+    assert_eq!(progress.last(), Some(&("upload_test.txt".to_string(), file_size, file_size)));
+
+    // the bar does not panic for an empty file and for a file that grew during the upload
+    let terminal_progress_bar = cargo_auto_github_lib::TerminalProgressBar::new();
+    cargo_auto_github_lib::UploadProgress::on_progress(&terminal_progress_bar, "empty.txt", 0, 0);
+    cargo_auto_github_lib::UploadProgress::on_progress(&terminal_progress_bar, "grew.txt", 50, 100);
+    cargo_auto_github_lib::UploadProgress::on_progress(&terminal_progress_bar, "grew.txt", 150, 100);
+}
+
+/// Client that sends the upload stream to the local server and the other requests to the mock
+struct MockWithLocalUploadGitHubClient {
+    mock_github_client: cargo_auto_github_lib::MockGitHubClient,
+    local_server_github_client: LocalServerGitHubClient,
+}
+
+impl cargo_auto_github_lib::SendToGitHubApiAsync for MockWithLocalUploadGitHubClient {
+    fn send_to_github_api_async(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> impl std::future::Future<Output = cargo_auto_github_lib::GitHubResponse> + Send {
+        let (client, request) = req.build_split();
+        let request = request.unwrap();
+        let is_upload = request.url().host_str() == Some("uploads.github.com");
+        let req = reqwest::RequestBuilder::from_parts(client, request);
+        async move {
+            if is_upload {
+                self.local_server_github_client.send_to_github_api_async(req).await
+            } else {
+                self.mock_github_client.send_to_github_api_async(req).await
+            }
+        }
+    }
+}
+
+#[test]
+fn upload_or_replace_asset_async_progress_callback() {
+    let github_client = MockWithLocalUploadGitHubClient {
+        mock_github_client: cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+            serde_json::json!([{"id": 11, "name": "upload_test.txt"}]),
+            serde_json::Value::Null,
+        ]),
+        local_server_github_client: LocalServerGitHubClient {
+            local_address: local_upload_server(),
+        },
+    };
+    let progress = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let progress_for_callback = progress.clone();
+    let upload_progress = move |file_name: &str, bytes_sent: u64, total_bytes: u64| {
+        progress_for_callback
+            .lock()
+            .unwrap()
+            .push((file_name.to_string(), bytes_sent, total_bytes));
+    };
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let asset = runtime
+        .block_on(cargo_auto_github_lib::upload_or_replace_asset_async(
+            &github_client,
+            "bestia-dev",
+            "hello_world",
+            5,
+            UPLOAD_TEST_FILE,
+            Some(std::sync::Arc::new(upload_progress)),
+        ))
+        .unwrap();
+    assert_eq!(asset.id, 31);
+
+    let requests = github_client.mock_github_client.recorded_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "DELETE");
+    let file_size = std::fs::metadata(UPLOAD_TEST_FILE).unwrap().len();
+    assert_eq!(
+        progress.lock().unwrap().last(),
+        Some(&("upload_test.txt".to_string(), file_size, file_size))
+    );
+}
+
+#[test]
+fn paginate_with_mock() {
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"total_count": 3, "items": [{"id": 1}, {"id": 2}]}),
+        serde_json::json!({"total_count": 3, "items": [{"id": 3}]}),
+    ]);
+    let items: Vec<serde_json::Value> = cargo_auto_github_lib::paginate(&github_client, 2, |per_page, page| {
+        cargo_auto_github_lib::github_api_list_releases("bestia-dev", "hello_world", per_page, page)
+    })
+    .collect::<Result<_, _>>()
+    .unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[2]["id"], 3);
+    let requests = github_client.recorded_requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].url.ends_with("per_page=2&page=2"));
+
+    let link_header = r#"<https://api.github.com/repositories/1/releases?page=2>; rel="next", <https://api.github.com/repositories/1/releases?page=5>; rel="last""#;
+    assert_eq!(
+        cargo_auto_github_lib::next_page_url_from_link_header(link_header).as_deref(),
+        Some("https://api.github.com/repositories/1/releases?page=2")
+    );
+    assert_eq!(
+        cargo_auto_github_lib::next_page_url_from_link_header(r#"<https://x>; rel="prev""#),
+        None
+    );
+}
+
+#[test]
+fn github_response_status_and_headers_with_mock() {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        "link",
+        r#"<https://api.github.com/repositories/1/releases?page=2>; rel="next""#.parse().unwrap(),
+    );
+    let github_client = cargo_auto_github_lib::MockGitHubClient::new();
+    github_client.push_github_response(cargo_auto_github_lib::GitHubResponse::new(
+        200,
+        headers,
+        serde_json::json!([{"id": 1, "tag_name": "v1.0.0"}]),
+    ));
+    github_client.push_github_response(cargo_auto_github_lib::GitHubResponse::new(
+        200,
+        reqwest::header::HeaderMap::new(),
+        serde_json::json!([{"id": 2, "tag_name": "v2.0.0"}]),
+    ));
+    github_client.push_github_response(cargo_auto_github_lib::GitHubResponse::new(
+        204,
+        reqwest::header::HeaderMap::new(),
+        serde_json::Value::Null,
+    ));
+    github_client.push_github_response(cargo_auto_github_lib::GitHubResponse::new(
+        404,
+        reqwest::header::HeaderMap::new(),
+        serde_json::json!({"message": "Not Found"}),
+    ));
+
+    // the Link header has the priority over the count of items
+    let releases = cargo_auto_github_lib::list_releases(&github_client, "bestia-dev", "hello_world").unwrap();
+    assert_eq!(releases.len(), 2);
+    assert_eq!(
+        github_client.recorded_requests()[1].url,
+        "https://api.github.com/repositories/1/releases?page=2"
+    );
+
+    cargo_auto_github_lib::delete_release(&github_client, "bestia-dev", "hello_world", 1).unwrap();
+
+    let error = cargo_auto_github_lib::delete_release(&github_client, "bestia-dev", "hello_world", 1).unwrap_err();
+    assert!(error.is_not_found());
+    match error {
+        cargo_auto_github_lib::GitHubLibError::GitHubApiError { status, .. } => assert_eq!(status, Some(404)),
+        _ => panic!("unexpected error {error}"),
+    }
+}
+
+/// GitHubClient that allows only some operations
+struct AllowlistGitHubClient {
+    mock_github_client: cargo_auto_github_lib::MockGitHubClient,
+    allowlist: cargo_auto_github_lib::GitHubRequestAllowlist,
+}
+
+impl cargo_auto_github_lib::SendToGitHubApi for AllowlistGitHubClient {
+    fn send_to_github_api(&self, req: reqwest::blocking::RequestBuilder) -> serde_json::Value {
+        // the check that cannot be bypassed, also for the RequestBuilder sent directly
+        let github_request = cargo_auto_github_lib::GitHubRequest::from_request_builder(&req).unwrap();
+        self.allowlist.check(&github_request).unwrap();
+        self.mock_github_client.send_to_github_api(req)
+    }
+
+    fn upload_to_github(&self, req: reqwest::RequestBuilder) -> impl std::future::Future<Output = serde_json::Value> + Send {
+        let (github_request, req) = cargo_auto_github_lib::GitHubRequest::split_async_request_builder(req).unwrap();
+        self.allowlist.check(&github_request).unwrap();
+        self.mock_github_client.upload_to_github(req)
+    }
+
+    fn authorize_github_request(
+        &self,
+        github_request: &cargo_auto_github_lib::GitHubRequest,
+    ) -> cargo_auto_github_lib::ResultWithGitHubLibError<()> {
+        self.allowlist.check(github_request)
+    }
+}
+
+#[test]
+fn github_request_allowlist_with_mock() {
+    let github_request = cargo_auto_github_lib::GitHubRequest::from_request_builder(&cargo_auto_github_lib::github_api_get_release_by_tag(
+        "bestia-dev",
+        "hello_world",
+        "v1.0.0",
+    ))
+    .unwrap();
+    assert_eq!(github_request.operation, "get_release_by_tag");
+    assert_eq!(github_request.method, "GET");
+    assert_eq!(github_request.path, "/repos/bestia-dev/hello_world/releases/tags/v1.0.0");
+
+    let github_client = AllowlistGitHubClient {
+        mock_github_client: cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+            serde_json::json!({"id": 1, "tag_name": "v1.0.0"}),
+        ]),
+        allowlist: cargo_auto_github_lib::GitHubRequestAllowlist::new(&["get_release_by_tag", "create_release"])
+            .allow("upload_release_asset"),
+    };
+    let release = cargo_auto_github_lib::get_release_by_tag(&github_client, "bestia-dev", "hello_world", "v1.0.0").unwrap();
+    assert_eq!(release.unwrap().id, 1);
+
+    // the delete is not allowed and it is never sent
+    let error = cargo_auto_github_lib::delete_release(&github_client, "bestia-dev", "hello_world", 1).unwrap_err();
+    assert!(matches!(
+        error,
+        cargo_auto_github_lib::GitHubLibError::RequestNotAllowedError { ref operation, .. } if operation == "delete_release"
+    ));
+    assert_eq!(github_client.mock_github_client.recorded_requests().len(), 1);
+
+    // the RequestBuilder sent directly does not pass the hook, but it does not pass send_to_github_api()
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        cargo_auto_github_lib::SendToGitHubApi::send_to_github_api(
+            &github_client,
+            cargo_auto_github_lib::github_api_delete_release("bestia-dev", "hello_world", 1),
+        )
+    }));
+    assert!(result.is_err());
+    assert_eq!(github_client.mock_github_client.recorded_requests().len(), 1);
+}
+
+#[test]
+fn typed_structs_with_mock() {
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"login": "bestia-dev", "id": 1, "name": "Bestia", "type": "User", "unknown_field": 1}),
+        serde_json::json!({"login": "automation-tasks-rs", "id": 2, "description": null}),
+        serde_json::json!({
+            "id": 3,
+            "name": "hello_world",
+            "full_name": "bestia-dev/hello_world",
+            "owner": {"login": "bestia-dev", "id": 1},
+            "description": null,
+            "private": true,
+            "default_branch": "main",
+            "topics": ["rust"],
+            "visibility": "private"
+        }),
+        serde_json::json!({
+            "id": 4,
+            "tag_name": "v1.0.0",
+            "prerelease": true,
+            "assets": [{"id": 5, "name": "hello_world.tar.gz", "state": "uploaded", "size": 10}]
+        }),
+        serde_json::json!({"id": 5, "name": "hello_world.tar.gz", "download_count": 7}),
+        serde_json::json!({"names": ["rust", "cargo-auto"]}),
+        // the mandatory field is missing
+        serde_json::json!({"id": 6}),
+        // the GitHub error is not deserialized
+        serde_json::json!({"message": "Not Found", "status": "404"}),
+    ]);
+
+    let user: cargo_auto_github_lib::User =
+        cargo_auto_github_lib::send_to_github_api_as(&github_client, cargo_auto_github_lib::github_api_get_authenticated_user()).unwrap();
+    assert_eq!(user.login, "bestia-dev");
+    assert_eq!(user.user_type.as_deref(), Some("User"));
+
+    let organization: cargo_auto_github_lib::Organization = cargo_auto_github_lib::send_to_github_api_as(
+        &github_client,
+        cargo_auto_github_lib::github_api_get_organization("automation-tasks-rs"),
+    )
+    .unwrap();
+    assert_eq!(organization.id, 2);
+    assert_eq!(organization.description, None);
+
+    let repository: cargo_auto_github_lib::Repository = cargo_auto_github_lib::send_to_github_api_as(
+        &github_client,
+        cargo_auto_github_lib::github_api_get_repository("bestia-dev", "hello_world"),
+    )
+    .unwrap();
+    assert_eq!(repository.owner.unwrap().login, "bestia-dev");
+    assert!(repository.private);
+    assert_eq!(repository.default_branch.as_deref(), Some("main"));
+    assert_eq!(repository.topics, vec!["rust"]);
+    assert!(!repository.has_wiki);
+
+    let release: cargo_auto_github_lib::Release = cargo_auto_github_lib::send_to_github_api_as(
+        &github_client,
+        cargo_auto_github_lib::github_api_get_release("bestia-dev", "hello_world", 4),
+    )
+    .unwrap();
+    assert!(release.prerelease);
+    assert!(!release.draft);
+    assert_eq!(release.assets[0].size, 10);
+
+    let release_asset: cargo_auto_github_lib::ReleaseAsset = cargo_auto_github_lib::send_to_github_api_as(
+        &github_client,
+        cargo_auto_github_lib::github_api_get_release_asset("bestia-dev", "hello_world", 5),
+    )
+    .unwrap();
+    assert_eq!(release_asset.download_count, 7);
+    assert_eq!(release_asset.state, None);
+
+    let topics: cargo_auto_github_lib::Topics = cargo_auto_github_lib::send_to_github_api_as(
+        &github_client,
+        cargo_auto_github_lib::github_api_replace_all_topics(
+            "bestia-dev",
+            "hello_world",
+            &vec!["rust".to_string(), "cargo-auto".to_string()],
+        ),
+    )
+    .unwrap();
+    assert_eq!(topics.names, vec!["rust", "cargo-auto"]);
+
+    let result: Result<cargo_auto_github_lib::Release, _> = cargo_auto_github_lib::send_to_github_api_as(
+        &github_client,
+        cargo_auto_github_lib::github_api_get_release("bestia-dev", "hello_world", 6),
+    );
+    assert!(matches!(result, Err(cargo_auto_github_lib::GitHubLibError::SerdeJsonError(_))));
+
+    let result: Result<cargo_auto_github_lib::Release, _> = cargo_auto_github_lib::send_to_github_api_as(
+        &github_client,
+        cargo_auto_github_lib::github_api_get_release("bestia-dev", "hello_world", 7),
+    );
+    assert!(result.is_err());
+    assert!(!matches!(result, Err(cargo_auto_github_lib::GitHubLibError::SerdeJsonError(_))));
+}
+
+#[test]
+fn update_repository_body() {
+    let update_repository = cargo_auto_github_lib::UpdateRepository::new()
+        .visibility(cargo_auto_github_lib::RepositoryVisibility::Private)
+        .default_branch("main")
+        .has_wiki(false);
+    let github_request = cargo_auto_github_lib::GitHubRequest::from_request_builder(&cargo_auto_github_lib::github_api_update_repository(
+        "bestia-dev",
+        "hello_world",
+        &update_repository,
+    ))
+    .unwrap();
+    assert_eq!(github_request.operation, "update_repository");
+    // only the fields that are set
+    assert_eq!(
+        github_request.body,
+        Some(serde_json::json!({"default_branch": "main", "has_wiki": false, "visibility": "private"}))
+    );
+}
+
+#[test]
+fn async_functions_with_mock() {
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"message": "Not Found", "status": "404"}),
+        serde_json::json!([]),
+        serde_json::json!({"id": 8, "tag_name": "v1.0.1"}),
+        serde_json::json!({"id": 13, "name": "upload_test.txt", "state": "uploaded"}),
+    ]);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let asset = runtime.block_on(async {
+        let release_id = cargo_auto_github_lib::create_or_update_release_async(
+            &github_client,
+            "bestia-dev",
+            "hello_world",
+            "v1.0.1",
+            "Version 1.0.1",
+            "main",
+            "notes",
+            &cargo_auto_github_lib::NewReleaseOptions::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(release_id, 8);
+        cargo_auto_github_lib::upload_asset_to_release_async(
+            &github_client,
+            "bestia-dev",
+            "hello_world",
+            release_id,
+            UPLOAD_TEST_FILE,
+            None,
+        )
+        .await
+        .unwrap()
+    });
+    assert_eq!(asset.id, 13);
+
+    let requests = github_client.recorded_requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[2].method, "POST");
+    assert_eq!(requests[2].url, "https://api.github.com/repos/bestia-dev/hello_world/releases");
+    assert_eq!(
+        requests[3].url,
+        "https://uploads.github.com/repos/bestia-dev/hello_world/releases/8/assets?name=upload_test.txt"
+    );
+}
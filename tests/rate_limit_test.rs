@@ -0,0 +1,46 @@
+// rate_limit_test.rs
+
+// The retry policy is stored for the whole library,
+// so this test is in its own file to run in a separate process.
+
+#[test]
+fn rate_limit_from_headers() {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-ratelimit-limit", "5000".parse().unwrap());
+    headers.insert("x-ratelimit-remaining", "4999".parse().unwrap());
+    headers.insert("x-ratelimit-reset", "1372700873".parse().unwrap());
+    headers.insert("x-ratelimit-used", "1".parse().unwrap());
+    headers.insert("x-ratelimit-resource", "core".parse().unwrap());
+    headers.insert("retry-after", "30".parse().unwrap());
+
+    let rate_limit = cargo_auto_github_lib::RateLimit::from_headers(&headers).unwrap();
+    assert_eq!(rate_limit.remaining, 4999);
+    assert_eq!(rate_limit.resource.as_deref(), Some("core"));
+    assert_eq!(rate_limit.duration_until_reset(), std::time::Duration::ZERO);
+    assert_eq!(
+        cargo_auto_github_lib::retry_after_from_headers(&headers),
+        Some(std::time::Duration::from_secs(30))
+    );
+    assert!(cargo_auto_github_lib::RateLimit::from_headers(&reqwest::header::HeaderMap::new()).is_none());
+}
+
+#[test]
+fn wait_for_rate_limit_with_mock() {
+    cargo_auto_github_lib::set_retry_policy(
+        cargo_auto_github_lib::RetryPolicy::no_retry().wait_for_rate_limit(std::time::Duration::from_secs(10)),
+    );
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"message": "API rate limit exceeded for user ID 1.", "status": "403"}),
+        // the reset is in the past, so it waits only one second
+        serde_json::json!({
+            "resources": {"core": {"limit": 5000, "remaining": 0, "reset": 0, "used": 5000, "resource": "core"}},
+            "rate": {"limit": 5000, "remaining": 0, "reset": 0, "used": 5000, "resource": "core"}
+        }),
+        serde_json::json!({"id": 1, "tag_name": "v1.0.0"}),
+    ]);
+    let release = cargo_auto_github_lib::get_release_by_tag(&github_client, "bestia-dev", "hello_world", "v1.0.0").unwrap();
+    assert_eq!(release.unwrap().id, 1);
+    let recorded_requests = github_client.recorded_requests();
+    assert_eq!(recorded_requests.len(), 3);
+    assert!(recorded_requests[1].url.ends_with("/rate_limit"));
+}
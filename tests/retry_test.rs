@@ -0,0 +1,157 @@
+// retry_test.rs
+
+// The retry policy is stored for the whole library,
+// so this test is in its own file to run in a separate process.
+
+#[test]
+fn retry_server_error_with_mock() {
+    cargo_auto_github_lib::set_retry_policy(
+        cargo_auto_github_lib::RetryPolicy::new(3).initial_backoff(std::time::Duration::from_millis(1)),
+    );
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"message": "Server Error", "status": "502"}),
+        serde_json::json!({"message": "You have exceeded a secondary rate limit.", "status": "403"}),
+        serde_json::json!({"id": 1, "tag_name": "v1.0.0"}),
+        // missing permission is not retried
+        serde_json::json!({"message": "Resource not accessible by personal access token", "status": "403"}),
+    ]);
+    let release = cargo_auto_github_lib::get_release_by_tag(&github_client, "bestia-dev", "hello_world", "v1.0.0").unwrap();
+    assert_eq!(release.unwrap().id, 1);
+    assert_eq!(github_client.recorded_requests().len(), 3);
+
+    let result = cargo_auto_github_lib::delete_release(&github_client, "bestia-dev", "hello_world", 1);
+    assert!(result.is_err());
+    assert_eq!(github_client.recorded_requests().len(), 4);
+
+    let retry_policy = cargo_auto_github_lib::retry_policy();
+    assert_eq!(retry_policy.backoff(1), std::time::Duration::from_millis(1));
+    assert_eq!(retry_policy.backoff(3), std::time::Duration::from_millis(4));
+}
+
+#[test]
+fn retry_rate_limit_json_without_status_with_mock() {
+    cargo_auto_github_lib::set_retry_policy(
+        cargo_auto_github_lib::RetryPolicy::new(3).initial_backoff(std::time::Duration::from_millis(1)),
+    );
+    // the json adapter of the GitHubClient has no status and no headers
+    let github_response = cargo_auto_github_lib::GitHubResponse::from_json(serde_json::json!({
+        "message": "API rate limit exceeded for user ID 1."
+    }));
+    assert!(cargo_auto_github_lib::retry_policy().is_retryable_response(&github_response));
+
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+        serde_json::json!({"message": "You have exceeded a secondary rate limit. Please wait a few minutes before you try again."}),
+        serde_json::json!({"id": 1, "tag_name": "v1.0.0"}),
+    ]);
+    let release = cargo_auto_github_lib::get_release_by_tag(&github_client, "bestia-dev", "hello_world", "v1.0.0").unwrap();
+    assert_eq!(release.unwrap().id, 1);
+    assert_eq!(github_client.recorded_requests().len(), 2);
+}
+
+#[test]
+fn retry_stream_body_is_authorized_and_sent_once() {
+    cargo_auto_github_lib::set_retry_policy(
+        cargo_auto_github_lib::RetryPolicy::new(3).initial_backoff(std::time::Duration::from_millis(1)),
+    );
+    let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![serde_json::json!({
+        "message": "Server Error",
+        "status": "502"
+    })]);
+    let stream = futures_util::stream::iter(vec![Ok::<_, std::io::Error>(b"upload".to_vec())]);
+    let req = reqwest::Client::new()
+        .post("https://uploads.github.com/repos/bestia-dev/hello_world/releases/5/assets?name=upload_test.txt")
+        .body(reqwest::Body::wrap_stream(stream));
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let github_response = runtime
+        .block_on(cargo_auto_github_lib::send_to_github_api_with_retry_async(&github_client, req))
+        .unwrap();
+    // the stream body cannot be cloned, so the request is not retried
+    assert_eq!(github_response.status, 502);
+    let requests = github_client.recorded_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].body, None);
+
+    // the client that does not allow the upload sees the operation of the stream request
+    let github_client = AllowlistGitHubClient {
+        mock_github_client: cargo_auto_github_lib::MockGitHubClient::new(),
+    };
+    let stream = futures_util::stream::iter(vec![Ok::<_, std::io::Error>(b"upload".to_vec())]);
+    let req = reqwest::Client::new()
+        .post("https://uploads.github.com/repos/bestia-dev/hello_world/releases/5/assets?name=upload_test.txt")
+        .body(reqwest::Body::wrap_stream(stream));
+    let result = runtime.block_on(cargo_auto_github_lib::send_to_github_api_with_retry_async(&github_client, req));
+    assert!(result.is_err());
+    assert!(github_client.mock_github_client.recorded_requests().is_empty());
+}
+
+/// Client that allows only reading the releases
+struct AllowlistGitHubClient {
+    mock_github_client: cargo_auto_github_lib::MockGitHubClient,
+}
+
+impl cargo_auto_github_lib::SendToGitHubApiAsync for AllowlistGitHubClient {
+    fn send_to_github_api_async(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> impl std::future::Future<Output = cargo_auto_github_lib::GitHubResponse> + Send {
+        self.mock_github_client.send_to_github_api_async(req)
+    }
+
+    fn authorize_github_request(
+        &self,
+        github_request: &cargo_auto_github_lib::GitHubRequest,
+    ) -> cargo_auto_github_lib::ResultWithGitHubLibError<()> {
+        cargo_auto_github_lib::GitHubRequestAllowlist::new(&["list_releases", "get_release_by_tag"]).check(github_request)
+    }
+}
+
+#[test]
+fn retry_upload_deletes_the_starter_asset() {
+    cargo_auto_github_lib::set_retry_policy(
+        cargo_auto_github_lib::RetryPolicy::new(3).initial_backoff(std::time::Duration::from_millis(1)),
+    );
+    let github_client = cargo_auto_github_lib::MockGitHubClient::new();
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("Retry-After", reqwest::header::HeaderValue::from_static("0"));
+    github_client.push_github_response(cargo_auto_github_lib::GitHubResponse::new(
+        403,
+        headers,
+        serde_json::json!({"message": "You have exceeded a secondary rate limit."}),
+    ));
+    github_client.push_response(serde_json::json!([]));
+    github_client.push_response(serde_json::json!({"message": "Server Error", "status": "502"}));
+    // the failed upload left the asset in the state starter
+    github_client.push_response(serde_json::json!([
+        {"id": 77, "name": "upload_test.txt", "state": "starter"},
+        {"id": 70, "name": "other.txt", "state": "uploaded"}
+    ]));
+    github_client.push_github_response(cargo_auto_github_lib::GitHubResponse::new(
+        204,
+        reqwest::header::HeaderMap::new(),
+        serde_json::Value::Null,
+    ));
+    github_client.push_response(serde_json::json!({"id": 78, "name": "upload_test.txt", "state": "uploaded"}));
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let asset = runtime
+        .block_on(cargo_auto_github_lib::upload_asset_to_release_async(
+            &github_client,
+            "bestia-dev",
+            "hello_world",
+            5,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/upload_test.txt"),
+            None,
+        ))
+        .unwrap();
+    assert_eq!(asset.id, 78);
+    let requests = github_client.recorded_requests();
+    assert_eq!(requests.len(), 6);
+    assert_eq!(requests[1].method, "GET");
+    assert_eq!(requests[4].method, "DELETE");
+    assert_eq!(
+        requests[4].url,
+        "https://api.github.com/repos/bestia-dev/hello_world/releases/assets/77"
+    );
+    assert_eq!(requests[5].method, "POST");
+}
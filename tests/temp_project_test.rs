@@ -0,0 +1,483 @@
+// temp_project_test.rs
+
+// The library reads Cargo.toml and runs git in the current directory.
+// These tests change the current directory of the whole process,
+// so they are in their own file to run in a separate process from the other tests.
+
+// region: tests with MockGitHubClient
+
+// The library reads Cargo.toml from the current directory.
+// The current directory is global for the process, so these tests must not run in parallel.
+// Every test in this file must run inside in_temp_project() that holds the mutex.
+static CURRENT_DIR_MUTEX: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Run the test inside a new temporary project directory with this Cargo.toml
+fn in_temp_project(test_name: &str, cargo_toml: &str, test_fn: impl FnOnce(&std::path::Path)) {
+    let _guard = CURRENT_DIR_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+    let project_dir = std::env::temp_dir().join(format!("cargo_auto_github_lib_{test_name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&project_dir);
+    std::fs::create_dir_all(project_dir.join("src")).unwrap();
+    std::fs::create_dir_all(project_dir.join("automation_tasks_rs")).unwrap();
+    std::fs::write(project_dir.join("src/lib.rs"), "").unwrap();
+    std::fs::write(project_dir.join("Cargo.toml"), cargo_toml).unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&project_dir).unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| test_fn(&project_dir)));
+    std::env::set_current_dir(original_dir).unwrap();
+    let _ = std::fs::remove_dir_all(&project_dir);
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+}
+
+const TEST_CARGO_TOML: &str = r#"
+[package]
+name = "hello_world"
+version = "1.0.0"
+edition = "2021"
+description = "Hello world for testing"
+repository = "https://github.com/bestia-dev/hello_world"
+keywords = ["rust", "cargo-auto"]
+"#;
+
+#[test]
+fn description_and_topics_to_github_with_mock() {
+    in_temp_project("description_and_topics", TEST_CARGO_TOML, |project_dir| {
+        let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+            serde_json::json!({"id": 1, "name": "hello_world", "full_name": "bestia-dev/hello_world", "description": "old description", "topics": ["rust"]}),
+            serde_json::json!({"id": 1, "name": "hello_world", "full_name": "bestia-dev/hello_world", "description": "Hello world for testing"}),
+            serde_json::json!({"names": ["rust", "cargo-auto"]}),
+        ]);
+        cargo_auto_github_lib::try_description_and_topics_to_github(&github_client).unwrap();
+
+        let requests = github_client.recorded_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].url, "https://api.github.com/repos/bestia-dev/hello_world");
+        assert_eq!(requests[0].header("X-GitHub-Api-Version"), Some("2022-11-28"));
+        assert_eq!(requests[1].method, "PATCH");
+        assert_eq!(requests[1].url, "https://api.github.com/repos/bestia-dev/hello_world");
+        assert_eq!(
+            requests[1].body,
+            Some(serde_json::json!({"description": "Hello world for testing"}))
+        );
+        assert_eq!(requests[2].method, "PUT");
+        assert_eq!(requests[2].url, "https://api.github.com/repos/bestia-dev/hello_world/topics");
+        assert_eq!(requests[2].body, Some(serde_json::json!({"names": ["rust", "cargo-auto"]})));
+        assert!(project_dir.join("automation_tasks_rs/.old_metadata.json").exists());
+
+        // the second time the old metadata is equal and there is no call to GitHub
+        let github_client = cargo_auto_github_lib::MockGitHubClient::new();
+        cargo_auto_github_lib::try_description_and_topics_to_github(&github_client).unwrap();
+        assert!(github_client.recorded_requests().is_empty());
+    });
+}
+
+const TEST_CARGO_TOML_WITH_METADATA: &str = r#"
+[package]
+name = "hello_world"
+version = "1.0.0"
+edition = "2021"
+description = "Hello world for testing"
+repository = "https://github.com/bestia-dev/hello_world"
+keywords = ["rust", "cargo-auto"]
+
+[package.metadata.github]
+visibility = "private"
+has_wiki = true
+default_branch = "main"
+pages = { build_type = "legacy", branch = "gh-pages", path = "/" }
+delete_branch_on_merge = true
+topics = ["automation", "rust"]
+"#;
+
+#[test]
+fn github_metadata_from_cargo_toml() {
+    in_temp_project("github_metadata", TEST_CARGO_TOML_WITH_METADATA, |project_dir| {
+        let github_metadata = cargo_auto_github_lib::cargo_toml_github_metadata().unwrap();
+        assert_eq!(
+            github_metadata.visibility,
+            Some(cargo_auto_github_lib::RepositoryVisibility::Private)
+        );
+        assert_eq!(github_metadata.has_issues, None);
+
+        // the sync sends only the settings that are different on GitHub
+        let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+            serde_json::json!({"id": 1, "name": "hello_world", "full_name": "bestia-dev/hello_world", "description": "Hello world for testing", "topics": ["rust", "cargo-auto"], "visibility": "public", "default_branch": "main", "has_wiki": false}),
+            serde_json::json!({"names": ["rust", "cargo-auto", "automation"]}),
+            serde_json::json!({"id": 1, "name": "hello_world", "full_name": "bestia-dev/hello_world"}),
+        ]);
+        cargo_auto_github_lib::try_description_and_topics_to_github(&github_client).unwrap();
+        let requests = github_client.recorded_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[1].body,
+            Some(serde_json::json!({"names": ["rust", "cargo-auto", "automation"]}))
+        );
+        assert_eq!(requests[2].method, "PATCH");
+        assert_eq!(
+            requests[2].body,
+            Some(serde_json::json!({"visibility": "private", "has_wiki": true, "delete_branch_on_merge": true}))
+        );
+
+        // the new repository is created with the same settings
+        let git_init = std::process::Command::new("git")
+            .arg("init")
+            .current_dir(project_dir)
+            .output()
+            .unwrap();
+        assert!(git_init.status.success());
+        let github_client = cargo_auto_github_lib::DryRunGitHubClient::new().user_login("bestia-dev");
+        cargo_auto_github_lib::try_new_remote_github_repository_with_prompter(
+            &github_client,
+            &cargo_auto_github_lib::FixedAnswerPrompter::yes(),
+        )
+        .unwrap();
+        let requests = github_client.planned_requests();
+        let body = requests[1].body.clone().unwrap();
+        assert_eq!(body["private"], true);
+        assert_eq!(body["has_wiki"], true);
+        assert_eq!(body["delete_branch_on_merge"], true);
+        assert_eq!(
+            requests[2].body,
+            Some(serde_json::json!({"build_type": "legacy", "source": {"branch": "gh-pages", "path": "/"}}))
+        );
+    });
+
+    // a typo in the table is an error
+    in_temp_project(
+        "github_metadata_typo",
+        &format!("{TEST_CARGO_TOML}\n[package.metadata.github]\nhas_wikki = true\n"),
+        |_project_dir| {
+            let result = cargo_auto_github_lib::cargo_toml_github_metadata();
+            assert!(matches!(result, Err(cargo_auto_github_lib::GitHubLibError::CargoTomlError(_))));
+        },
+    );
+}
+
+#[test]
+fn repository_metadata_sync_reports_changes() {
+    let cargo_toml = r#"
+[package]
+name = "hello_world"
+version = "1.0.0"
+edition = "2021"
+description = "Hello world for testing"
+repository = "https://github.com/bestia-dev/hello_world"
+documentation = "https://docs.rs/hello_world"
+keywords = ["rust"]
+categories = ["development-tools::build-utils"]
+
+[package.metadata.github]
+has_issues = false
+"#;
+    in_temp_project("metadata_sync", cargo_toml, |project_dir| {
+        let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+            serde_json::json!({"id": 1, "name": "hello_world", "full_name": "bestia-dev/hello_world", "description": "Hello world for testing", "topics": ["rust"], "homepage": "", "has_issues": true}),
+            serde_json::json!({"names": ["rust", "development-tools-build-utils"]}),
+            serde_json::json!({"id": 1, "name": "hello_world", "full_name": "bestia-dev/hello_world"}),
+        ]);
+        let changes = cargo_auto_github_lib::try_description_and_topics_to_github(&github_client).unwrap();
+        let requests = github_client.recorded_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[2].body,
+            Some(serde_json::json!({"homepage": "https://docs.rs/hello_world", "has_issues": false}))
+        );
+        let fields: Vec<&str> = changes.iter().map(|change| change.field.as_str()).collect();
+        assert_eq!(fields, vec!["topics", "has_issues", "homepage"]);
+        assert_eq!(changes[0].new_value, serde_json::json!(["rust", "development-tools-build-utils"]));
+        assert_eq!(changes[1].to_string(), "has_issues: true -> false");
+        assert_eq!(changes[2].old_value, serde_json::json!(""));
+
+        // the second time nothing changed
+        let github_client = cargo_auto_github_lib::MockGitHubClient::new();
+        let changes = cargo_auto_github_lib::try_description_and_topics_to_github(&github_client).unwrap();
+        assert!(changes.is_empty());
+        assert!(github_client.recorded_requests().is_empty());
+
+        // the new repository gets the same homepage as the sync
+        git(project_dir, &["init"]);
+        let github_client = cargo_auto_github_lib::DryRunGitHubClient::new().user_login("bestia-dev");
+        cargo_auto_github_lib::try_new_remote_github_repository_with_prompter(
+            &github_client,
+            &cargo_auto_github_lib::FixedAnswerPrompter::yes(),
+        )
+        .unwrap();
+        let requests = github_client.planned_requests();
+        assert_eq!(requests[1].body.as_ref().unwrap()["homepage"], "https://docs.rs/hello_world");
+    });
+}
+
+#[test]
+fn new_remote_github_repository_unknown_organization_with_mock() {
+    in_temp_project("unknown_organization", TEST_CARGO_TOML, |_project_dir| {
+        let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+            serde_json::json!({"login": "someone-else", "id": 1}),
+            serde_json::json!({"message": "Not Found", "status": "404"}),
+        ]);
+        let result = cargo_auto_github_lib::try_new_remote_github_repository(&github_client);
+        assert!(matches!(
+            result,
+            Err(cargo_auto_github_lib::GitHubLibError::GitHubApiError { status: Some(404), .. })
+        ));
+
+        let requests = github_client.recorded_requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].url, "https://api.github.com/user");
+        assert_eq!(requests[1].method, "GET");
+        assert_eq!(requests[1].url, "https://api.github.com/orgs/bestia-dev");
+    });
+}
+
+#[test]
+fn new_remote_github_repository_placeholder_owner_with_mock() {
+    let cargo_toml = TEST_CARGO_TOML.replace("bestia-dev", "github_owner");
+    in_temp_project("placeholder_owner", &cargo_toml, |_project_dir| {
+        let github_client = cargo_auto_github_lib::MockGitHubClient::new();
+        let result = cargo_auto_github_lib::try_new_remote_github_repository(&github_client);
+        assert!(matches!(result, Err(cargo_auto_github_lib::GitHubLibError::CargoTomlError(_))));
+        assert!(github_client.recorded_requests().is_empty());
+    });
+}
+
+#[test]
+fn try_functions_return_error_for_bad_cargo_toml() {
+    // the virtual workspace has no package and the malformed toml cannot be parsed
+    for (test_name, cargo_toml) in [
+        ("virtual_workspace", "[workspace]\nmembers = []\n"),
+        ("malformed_toml", "[package\n"),
+    ] {
+        in_temp_project(test_name, cargo_toml, |_project_dir| {
+            let github_client = cargo_auto_github_lib::MockGitHubClient::new();
+            let result = cargo_auto_github_lib::try_new_remote_github_repository(&github_client);
+            assert!(matches!(result, Err(cargo_auto_github_lib::GitHubLibError::CargoTomlError(_))));
+            let result = cargo_auto_github_lib::try_description_and_topics_to_github(&github_client);
+            assert!(matches!(result, Err(cargo_auto_github_lib::GitHubLibError::CargoTomlError(_))));
+            assert!(github_client.recorded_requests().is_empty());
+        });
+    }
+}
+
+#[test]
+fn git_has_remote_outside_of_git_repository() {
+    in_temp_project("outside_of_git", TEST_CARGO_TOML, |_project_dir| {
+        assert!(!cargo_auto_github_lib::try_git_has_remote().unwrap());
+        assert!(!cargo_auto_github_lib::try_git_has_upstream().unwrap());
+        assert!(!cargo_auto_github_lib::git_has_remote());
+        assert!(!cargo_auto_github_lib::git_has_upstream());
+    });
+}
+
+#[test]
+fn description_and_topics_to_github_dry_run() {
+    in_temp_project("dry_run", TEST_CARGO_TOML, |project_dir| {
+        let github_client = cargo_auto_github_lib::DryRunGitHubClient::new().user_login("bestia-dev");
+        cargo_auto_github_lib::try_description_and_topics_to_github(&github_client).unwrap();
+
+        let requests = github_client.planned_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[1].method, "PATCH");
+        assert_eq!(
+            requests[1].body,
+            Some(serde_json::json!({"description": "Hello world for testing"}))
+        );
+        assert_eq!(requests[2].method, "PUT");
+        assert_eq!(requests[2].url, "https://api.github.com/repos/bestia-dev/hello_world/topics");
+        // the dry run does not change local files
+        assert!(!project_dir.join("automation_tasks_rs/.old_metadata.json").exists());
+
+        let github_client = cargo_auto_github_lib::DryRunGitHubClient::new();
+        let release_id = cargo_auto_github_lib::create_or_update_release(
+            &github_client,
+            "bestia-dev",
+            "hello_world",
+            "v1.0.0",
+            "Version 1.0.0",
+            "main",
+            "notes",
+            &cargo_auto_github_lib::NewReleaseOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(release_id, 0);
+        let requests = github_client.planned_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].method, "POST");
+        assert_eq!(requests[2].url, "https://api.github.com/repos/bestia-dev/hello_world/releases");
+    });
+}
+
+#[test]
+fn new_remote_github_repository_with_prompter() {
+    in_temp_project("prompter", TEST_CARGO_TOML, |project_dir| {
+        let git_init = std::process::Command::new("git")
+            .arg("init")
+            .current_dir(project_dir)
+            .output()
+            .unwrap();
+        assert!(git_init.status.success());
+
+        // the answer no stops before any mutating request
+        let github_client =
+            cargo_auto_github_lib::MockGitHubClient::with_responses(vec![serde_json::json!({"login": "bestia-dev", "id": 1})]);
+        let prompter = cargo_auto_github_lib::ScriptedPrompter::new(vec![false]);
+        let result = cargo_auto_github_lib::try_new_remote_github_repository_with_prompter(&github_client, &prompter).unwrap();
+        assert_eq!(result, None);
+        assert_eq!(prompter.questions(), vec!["Do you want to create a new remote GitHub repository?"]);
+        assert_eq!(github_client.recorded_requests().len(), 1);
+
+        // the answer yes in the dry run plans the new repository and the pages site
+        let github_client = cargo_auto_github_lib::DryRunGitHubClient::new().user_login("bestia-dev");
+        let result = cargo_auto_github_lib::try_new_remote_github_repository_with_prompter(
+            &github_client,
+            &cargo_auto_github_lib::FixedAnswerPrompter::yes(),
+        )
+        .unwrap();
+        assert_eq!(result, Some(()));
+        let requests = github_client.planned_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(requests[1].url, "https://api.github.com/user/repos");
+        assert_eq!(requests[2].url, "https://api.github.com/repos/bestia-dev/hello_world/pages");
+        // the dry run does not add the git remote
+        assert!(!cargo_auto_github_lib::try_git_has_remote().unwrap());
+
+        // without a scripted answer the prompter returns an error instead of hanging
+        let prompter = cargo_auto_github_lib::ScriptedPrompter::new(vec![]);
+        let result = cargo_auto_github_lib::try_new_remote_github_repository_with_prompter(&github_client, &prompter);
+        assert!(matches!(result, Err(cargo_auto_github_lib::GitHubLibError::ErrorFromString(_))));
+    });
+}
+
+/// Run git in the directory and assert success
+fn git(dir: &std::path::Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git").args(args).current_dir(dir).output().unwrap();
+    assert!(
+        output.status.success(),
+        "git {}: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn new_remote_github_repository_push_on_top_of_auto_init() {
+    in_temp_project("auto_init", TEST_CARGO_TOML, |project_dir| {
+        // the remote with the initial commit like GitHub creates it with auto_init
+        let remote_dir = project_dir.with_extension("git");
+        let seed_dir = project_dir.with_extension("seed");
+        let _ = std::fs::remove_dir_all(&remote_dir);
+        let _ = std::fs::remove_dir_all(&seed_dir);
+        git(project_dir, &["init", "--bare", "-b", "main", remote_dir.to_str().unwrap()]);
+        git(project_dir, &["init", "-b", "main", seed_dir.to_str().unwrap()]);
+        std::fs::write(seed_dir.join("README.md"), "# hello_world").unwrap();
+        git(&seed_dir, &["add", "-A"]);
+        git(
+            &seed_dir,
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@test",
+                "commit",
+                "-m",
+                "Initial commit",
+            ],
+        );
+        git(&seed_dir, &["push", remote_dir.to_str().unwrap(), "main"]);
+
+        // the local project has its own history on another branch name
+        git(project_dir, &["init", "-b", "trunk"]);
+        git(project_dir, &["config", "user.name", "test"]);
+        git(project_dir, &["config", "user.email", "test@test"]);
+        git(project_dir, &["add", "-A"]);
+        git(project_dir, &["commit", "-m", "local commit"]);
+        git(project_dir, &["remote", "add", "origin", remote_dir.to_str().unwrap()]);
+
+        let github_client = cargo_auto_github_lib::MockGitHubClient::with_responses(vec![
+            serde_json::json!({"login": "bestia-dev", "id": 1}),
+            serde_json::json!({"id": 1, "name": "hello_world", "full_name": "bestia-dev/hello_world", "default_branch": "develop"}),
+            serde_json::json!({"url": "https://api.github.com/repos/bestia-dev/hello_world/pages"}),
+        ]);
+        let result = cargo_auto_github_lib::try_new_remote_github_repository_with_options(
+            &github_client,
+            &cargo_auto_github_lib::FixedAnswerPrompter::yes(),
+            &cargo_auto_github_lib::NewRepositoryOptions::new()
+                .auto_init(true)
+                .default_branch("develop"),
+        );
+        let remote_log = git(&remote_dir, &["log", "--format=%s", "develop"]);
+        let _ = std::fs::remove_dir_all(&remote_dir);
+        let _ = std::fs::remove_dir_all(&seed_dir);
+        assert_eq!(result.unwrap(), Some(()));
+        // the local commit is merged with the initial commit from main and pushed to the default_branch
+        assert!(remote_log.contains("local commit"));
+        assert!(remote_log.contains("Initial commit"));
+        assert!(cargo_auto_github_lib::try_git_has_upstream().unwrap());
+        let requests = github_client.recorded_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].method, "PATCH");
+        assert_eq!(requests[1].body.as_ref().unwrap()["default_branch"], "develop");
+    });
+}
+
+#[test]
+fn new_remote_github_repository_from_template_dry_run() {
+    in_temp_project("template", TEST_CARGO_TOML, |project_dir| {
+        let git_init = std::process::Command::new("git")
+            .arg("init")
+            .current_dir(project_dir)
+            .output()
+            .unwrap();
+        assert!(git_init.status.success());
+
+        let new_repository_options = cargo_auto_github_lib::NewRepositoryOptions::new()
+            .visibility(cargo_auto_github_lib::RepositoryVisibility::Private)
+            .template(cargo_auto_github_lib::RepositoryTemplate::new("automation-tasks-rs", "crate_template").include_all_branches(true));
+        let github_client = cargo_auto_github_lib::DryRunGitHubClient::new().user_login("bestia-dev");
+        let result = cargo_auto_github_lib::try_new_remote_github_repository_with_options(
+            &github_client,
+            &cargo_auto_github_lib::FixedAnswerPrompter::yes(),
+            &new_repository_options,
+        )
+        .unwrap();
+        assert_eq!(result, Some(()));
+        let requests = github_client.planned_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[1].url,
+            "https://api.github.com/repos/automation-tasks-rs/crate_template/generate"
+        );
+        let body = requests[1].body.clone().unwrap();
+        assert_eq!(body["owner"], "bestia-dev");
+        assert_eq!(body["name"], "hello_world");
+        assert_eq!(body["include_all_branches"], true);
+        assert_eq!(body["private"], true);
+        let github_request = cargo_auto_github_lib::GitHubRequest::from_url(reqwest::Method::POST, &requests[1].url, None);
+        assert_eq!(github_request.operation, "create_repository_from_template");
+        assert_eq!(requests[2].url, "https://api.github.com/repos/bestia-dev/hello_world/pages");
+    });
+}
+
+#[test]
+fn new_remote_github_repository_internal_user_repository() {
+    let new_repository_options =
+        cargo_auto_github_lib::NewRepositoryOptions::new().visibility(cargo_auto_github_lib::RepositoryVisibility::Internal);
+    // a user repository cannot be internal
+    in_temp_project("internal", TEST_CARGO_TOML, |_project_dir| {
+        let github_client = cargo_auto_github_lib::DryRunGitHubClient::new().user_login("bestia-dev");
+        let result = cargo_auto_github_lib::try_new_remote_github_repository_with_options(
+            &github_client,
+            &cargo_auto_github_lib::FixedAnswerPrompter::yes(),
+            &new_repository_options,
+        );
+        assert!(matches!(result, Err(cargo_auto_github_lib::GitHubLibError::ErrorFromString(_))));
+        assert_eq!(github_client.planned_requests().len(), 1);
+    });
+}
+
+// endregion: tests with MockGitHubClient